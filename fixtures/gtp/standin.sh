@@ -0,0 +1,21 @@
+#!/bin/sh
+# A minimal GTP engine used to test the GTP client. It answers every
+# genmove with the vertex given on the command line (pass by default)
+# and appends the commands it receives to the log file, if given. With
+# "hang" as the move it never answers genmove.
+move=${1:-pass}
+log=$2
+while read -r command args; do
+    [ -n "$log" ] && echo "$command $args" >> "$log"
+    case "$command" in
+        "")               continue ;;
+        name)             printf '= Stand-in\n\n' ;;
+        version)          printf '= 1.0\n\n' ;;
+        protocol_version) printf '= 2\n\n' ;;
+        genmove)          [ "$move" = hang ] && exec sleep 1000
+                          printf '= %s\n\n' "$move" ;;
+        boardsize|clear_board|komi|play|time_settings|time_left) printf '= \n\n' ;;
+        quit)             printf '= \n\n'; exit 0 ;;
+        *)                printf '? unknown command\n\n' ;;
+    esac
+done
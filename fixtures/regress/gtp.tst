@@ -0,0 +1,27 @@
+# Tests of the GTP interface and the rules. They don't depend on the
+# engine, so they can run with any of them.
+
+1 known_command loadsgf
+#? [true]
+
+loadsgf ../sgf/twomoves.sgf
+2 genmove b
+#? [!D15|Q7]
+
+loadsgf ../sgf/twomoves.sgf 2
+3 reg_genmove w
+#? [!D15]
+
+boardsize 5
+clear_board
+play b a2
+play w a3
+play b b2
+play w b3
+play b b1
+play w c2
+play b e5
+play w c1
+play b e4
+4 tsumego_status b2
+#? [dead A1]
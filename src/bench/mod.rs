@@ -0,0 +1,90 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use engine::Engine;
+use engine::McConfig;
+use engine::McEngine;
+use playout::Playout;
+use sgf::Parser;
+use sgf::collection::load;
+use sgf::collection::sgf_files;
+
+use std::rand::SeedableRng;
+use std::rand::XorShiftRng;
+use time::precise_time_ns;
+
+mod test;
+
+// The seed of all random number generators, so that every run plays
+// exactly the same playouts.
+pub static SEED: [u32; 4] = [0x193a6754, 0xa8a7d469, 0x97830e05, 0x113ba7bb];
+
+// The empty boards and the positions at the end of the SGF files in
+// the directory, by name. Files that can't be loaded are skipped.
+pub fn positions(directory: Path) -> Vec<(String, Parser)> {
+    let mut positions: Vec<(String, Parser)> = vec!(9, 13, 19).into_iter()
+        .map(|size| (format!("empty {}x{}", size, size), Parser::new(format!("(;GM[1]FF[4]SZ[{}]KM[6.5])", size))))
+        .collect();
+    for path in sgf_files(&directory).iter() {
+        match load(path) {
+            Some(parser) => positions.push((String::from_str(path.filename_str().unwrap()), parser)),
+            None         => {}
+        }
+    }
+    positions
+}
+
+// Runs the given number of playouts from the position. Returns the
+// number of moves played and the time it took in seconds.
+pub fn playouts(parser: &Parser, count: usize) -> (usize, f64) {
+    let game = parser.game().unwrap();
+    let playout = Playout::new(game.board());
+    let mut rng: XorShiftRng = SeedableRng::from_seed(SEED);
+    let mut moves = 0;
+    let start = precise_time_ns();
+    for _ in range(0, count) {
+        moves += playout.run_with_rng(&mut rng).moves().len();
+    }
+    (moves, (precise_time_ns() - start) as f64 / 1e9)
+}
+
+// Prints the playout speed and the time a full gen_move takes on
+// each of the positions.
+pub fn run(config: McConfig, playout_count: usize, directory: Path) {
+    let positions = positions(directory);
+    println!("Playouts ({} per position)", playout_count);
+    println!("{:<28} {:>8} {:>12} {:>12}", "position", "time(s)", "moves", "playouts/s");
+    for &(ref name, ref parser) in positions.iter() {
+        let (moves, time) = playouts(parser, playout_count);
+        println!("{:<28} {:>8.2} {:>12} {:>12.0}", name, time, moves, playout_count as f64 / time);
+    }
+    println!("");
+    println!("gen_move ({} playouts per candidate move)", config.playouts);
+    println!("{:<28} {:>8} {:>6}", "position", "time(s)", "move");
+    for &(ref name, ref parser) in positions.iter() {
+        let game = parser.game().unwrap();
+        let engine = McEngine::with_config(config).seeded(SEED);
+        let start = precise_time_ns();
+        let m = engine.gen_move(game.next_player(), &game);
+        let time = (precise_time_ns() - start) as f64 / 1e9;
+        println!("{:<28} {:>8.2} {:>6}", name, time, m.to_gtp());
+    }
+}
@@ -0,0 +1,49 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use super::playouts;
+use super::positions;
+
+#[test]
+fn the_positions_include_the_empty_boards_and_the_sgf_files() {
+    let positions = positions(Path::new("fixtures/sgf"));
+    let names: Vec<&str> = positions.iter().map(|&(ref name, _)| name.as_slice()).collect();
+    assert_eq!("empty 9x9", names[0]);
+    assert_eq!("empty 19x19", names[2]);
+    assert!(names.contains(&"twomoves.sgf"));
+    assert!(positions.iter().all(|&(_, ref parser)| parser.game().is_ok()));
+}
+
+#[test]
+fn invalid_sgf_files_are_skipped() {
+    let positions = positions(Path::new("fixtures/sgf-invalid"));
+    assert_eq!(3, positions.len());
+}
+
+#[test]
+fn the_playouts_are_the_same_in_every_run() {
+    let positions = positions(Path::new("fixtures/sgf"));
+    let (first, _) = playouts(&positions[0].1, 5);
+    let (second, _) = playouts(&positions[0].1, 5);
+    assert_eq!(first, second);
+}
@@ -19,6 +19,7 @@
  * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
  *                                                                      *
  ************************************************************************/
+use board::Symmetry;
 use core::fmt;
 use std::cmp::Eq;
 
@@ -68,6 +69,11 @@ impl Coord {
         (self.col as usize-1 + (self.row as usize-1)*board_size as usize)
     }
 
+    pub fn transform(&self, symmetry: Symmetry, board_size: u8) -> Coord {
+        let (x, y) = symmetry.apply(self.col - 1, self.row - 1, board_size);
+        Coord::new(x + 1, y + 1)
+    }
+
     pub fn is_inside(&self, board_size: u8) -> bool {
         1 <= self.col && self.col <= board_size && 1 <= self.row && self.row <= board_size
     }
@@ -24,6 +24,7 @@ pub use board::coord::Coord;
 pub use board::movement::Move;
 pub use board::movement::Pass;
 pub use board::movement::Play;
+pub use board::symmetry::Symmetry;
 pub use self::Color::Black;
 pub use self::Color::Empty;
 pub use self::Color::White;
@@ -42,6 +43,7 @@ mod chain;
 mod point;
 pub mod coord;
 pub mod movement;
+pub mod symmetry;
 
 #[derive(Show, Eq, PartialEq)]
 pub enum IllegalMove {
@@ -78,6 +80,14 @@ impl Color {
             err           => panic!("Can't read the GTP color: {}", err)
         }
     }
+
+    pub fn to_gtp(&self) -> String {
+        match *self {
+            White => String::from_str("w"),
+            Black => String::from_str("b"),
+            Empty => panic!("Empty has no GTP color")
+        }
+    }
 }
 
 #[derive(Show)]
@@ -89,6 +99,7 @@ pub struct Board<'a> {
     friend_stones_removed: Vec<Coord>,
     ko:                    Option<Coord>,
     komi:                  f32,
+    last_move:             Option<Move>,
     neighbours:            Rc<Vec<Vec<Coord>>>,
     previous_player:       Color,
     ruleset:               Ruleset,
@@ -106,6 +117,7 @@ impl<'a> Clone for Board<'a> {
             friend_stones_removed: self.friend_stones_removed.clone(),
             ko:                    self.ko,
             komi:                  self.komi,
+            last_move:             self.last_move,
             neighbours:            self.neighbours.clone(),
             previous_player:       self.previous_player,
             ruleset:               self.ruleset.clone(),
@@ -125,6 +137,7 @@ impl<'a> Board<'a> {
             friend_stones_removed: Vec::new(),
             ko:                    None,
             komi:                  komi,
+            last_move:             None,
             neighbours:            Board::setup_neighbours(size),
             previous_player:       White,
             ruleset:               ruleset,
@@ -174,6 +187,14 @@ impl<'a> Board<'a> {
         self.komi
     }
 
+    pub fn ko(&self) -> Option<Coord> {
+        self.ko
+    }
+
+    pub fn last_move(&self) -> Option<Move> {
+        self.last_move
+    }
+
     pub fn set_komi(&mut self, komi: f32) {
         self.komi = komi;
     }
@@ -259,7 +280,11 @@ impl<'a> Board<'a> {
             Ok(_)  => {}
         }
         self.previous_player = *m.color();
+        self.last_move = Some(m);
         if m.is_pass() {
+            // The ko ban only applies to the move right after the
+            // capture.
+            self.ko = None;
             self.consecutive_passes += 1;
             return Ok(());
         }
@@ -275,20 +300,111 @@ impl<'a> Board<'a> {
         // Adds removed stones as liberties to the neighbouring chains
         self.add_removed_adv_stones_as_libs(&m);
         // Checks for suicide play
+        self.friend_stones_removed = vec!();
         if self.get_chain(m.coord()).unwrap().is_captured() {
             self.friend_stones_removed = self.remove_suicide_chain(&m);
             self.add_removed_friendly_stones_as_libs(&m);
         }
-        if self.adv_stones_removed.len() == 1 && self.friend_stones_removed.len() == 0 {
+        // It's only a ko if the capturing stone could be captured
+        // right back, i.e. if it's a single stone with one liberty.
+        if self.adv_stones_removed.len() == 1 && self.friend_stones_removed.len() == 0 && self.is_single_stone_in_atari(m.coord()) {
             let coord = self.adv_stones_removed[0];
             self.ko = Some(coord);
         } else {
             self.ko = None;
         }
         self.update_vacant(&m);
+        if cfg!(not(ndebug)) {
+            match self.check_invariants() {
+                Ok(_)  => {},
+                Err(e) => panic!("{} after {:?}\n{}", e, m, self.as_string())
+            }
+        }
         Ok(())
     }
 
+    // Recomputes the chains and their liberties from the stones on
+    // the board and compares them with the incrementally updated
+    // state. This runs after every move in debug builds.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let coords = Coord::for_board_size(self.size);
+        let empty: HashSet<Coord> = coords.iter().filter(|c| self.color(c) == Empty).cloned().collect();
+        let vacant: HashSet<Coord> = self.vacant.iter().cloned().collect();
+        if vacant.len() != self.vacant.len() {
+            return Err(format!("The vacant points contain duplicates: {:?}", self.vacant));
+        }
+        if vacant != empty {
+            return Err(format!("The vacant points {:?} aren't the empty points {:?}", vacant, empty));
+        }
+        for (id, chain) in self.chains.iter().enumerate() {
+            if chain.id() != id {
+                return Err(format!("Chain {} has the id {}", id, chain.id()));
+            }
+            if chain.coords().len() == 0 || chain.color() == Empty {
+                return Err(format!("Chain {} is empty: {}", id, chain.show()));
+            }
+            for c in chain.coords().iter() {
+                if self.color(c) != chain.color() || self.chain_id(c) != id {
+                    return Err(format!("The point {:?} doesn't belong to chain {}", c, chain.show()));
+                }
+            }
+            let stones: HashSet<Coord> = chain.coords().iter().cloned().collect();
+            let (expected_stones, expected_libs) = self.flood_fill(chain.coords()[0]);
+            if stones.len() != chain.coords().len() || stones != expected_stones {
+                return Err(format!("Chain {} should have the stones {:?}", chain.show(), expected_stones));
+            }
+            if *chain.liberties() != expected_libs {
+                return Err(format!("Chain {} should have the liberties {:?}", chain.show(), expected_libs));
+            }
+        }
+        for c in coords.iter().filter(|c| self.color(c) != Empty) {
+            let id = self.chain_id(c);
+            if id >= self.chains.len() || !self.chains[id].coords().contains(c) {
+                return Err(format!("The stone at {:?} has the invalid chain id {}", c, id));
+            }
+        }
+        match self.ko {
+            Some(c) if self.color(&c) != Empty => Err(format!("The ko point {:?} isn't empty", c)),
+            _                                  => Ok(())
+        }
+    }
+
+    // The stones of the chain at the coord and its liberties.
+    fn flood_fill(&self, start: Coord) -> (HashSet<Coord>, HashSet<Coord>) {
+        let color = self.color(&start);
+        let mut stones = HashSet::new();
+        let mut libs = HashSet::new();
+        let mut stack = vec!(start);
+        stones.insert(start);
+        loop {
+            let c = match stack.pop() {
+                Some(c) => c,
+                None    => break
+            };
+            for &n in self.neighbours(c).iter() {
+                if self.color(&n) == Empty {
+                    libs.insert(n);
+                } else if self.color(&n) == color && stones.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        (stones, libs)
+    }
+
+    fn is_single_stone_in_atari(&self, c: Coord) -> bool {
+        let chain = self.get_chain(c).unwrap();
+        chain.coords().len() == 1 && chain.liberties().len() == 1
+    }
+
+    // Lets the player to move play elsewhere on the board. This is
+    // used by the tactical readers to model a tenuki: Unlike a pass
+    // it lifts the ko ban and doesn't count towards ending the game.
+    pub fn tenuki(&mut self) {
+        self.previous_player = self.next_player();
+        self.ko = None;
+    }
+
     fn update_vacant(&mut self, m: &Move) {
         let pos = self.vacant.iter().position(|&c| c == m.coord()).unwrap();
         self.vacant.swap_remove(pos);
@@ -317,7 +433,7 @@ impl<'a> Board<'a> {
     fn add_removed_friendly_stones_as_libs(&mut self, m: &Move) {
         let color = m.color().opposite();
         let mut libs: HashMap<Coord, Vec<usize>> = HashMap::new();
-        for &coord in self.adv_stones_removed.iter() {
+        for &coord in self.friend_stones_removed.iter() {
             let chain_ids = self.neighbours(coord)
                 .iter()
                 .filter(|&c| self.color(c) == color)
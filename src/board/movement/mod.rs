@@ -23,6 +23,7 @@ pub use self::Move::Pass;
 pub use self::Move::Play;
 use board::Color;
 use board::Coord;
+use board::Symmetry;
 
 mod test;
 
@@ -67,6 +68,16 @@ impl Move {
         }
     }
 
+    pub fn transform(&self, symmetry: Symmetry, board_size: u8) -> Move {
+        match *self {
+            Pass(color)           => Pass(color),
+            Play(color, col, row) => {
+                let coord = Coord::new(col, row).transform(symmetry, board_size);
+                Play(color, coord.col, coord.row)
+            }
+        }
+    }
+
     pub fn is_pass(&self) -> bool {
         match *self {
             Pass(_) => true,
@@ -0,0 +1,102 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+pub use self::Symmetry::AntiTranspose;
+pub use self::Symmetry::FlipHorizontal;
+pub use self::Symmetry::FlipVertical;
+pub use self::Symmetry::Identity;
+pub use self::Symmetry::Rotate180;
+pub use self::Symmetry::Rotate270;
+pub use self::Symmetry::Rotate90;
+pub use self::Symmetry::Transpose;
+use board::Board;
+use board::Color;
+use board::Coord;
+
+mod test;
+
+// The 8 symmetries of a square board (the dihedral group D4).
+#[derive(Clone, Copy, Show, Eq, PartialEq, Hash)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose
+}
+
+impl Symmetry {
+    pub fn all() -> Vec<Symmetry> {
+        vec!(Identity, Rotate90, Rotate180, Rotate270, FlipHorizontal, FlipVertical, Transpose, AntiTranspose)
+    }
+
+    pub fn inverse(&self) -> Symmetry {
+        match *self {
+            Rotate90  => Rotate270,
+            Rotate270 => Rotate90,
+            other     => other
+        }
+    }
+
+    // Transforms 0-based coordinates on a board of the given size.
+    pub fn apply(&self, x: u8, y: u8, size: u8) -> (u8, u8) {
+        let m = size - 1;
+        match *self {
+            Identity       => (x, y),
+            Rotate90       => (m - y, x),
+            Rotate180      => (m - x, m - y),
+            Rotate270      => (y, m - x),
+            FlipHorizontal => (m - x, y),
+            FlipVertical   => (x, m - y),
+            Transpose      => (y, x),
+            AntiTranspose  => (m - y, m - x)
+        }
+    }
+}
+
+// A read only view of a board as it looks after applying the
+// symmetry to it.
+pub struct TransformedBoard<'a> {
+    board:    &'a Board<'a>,
+    symmetry: Symmetry,
+}
+
+impl<'a> TransformedBoard<'a> {
+    pub fn new(board: &'a Board<'a>, symmetry: Symmetry) -> TransformedBoard<'a> {
+        TransformedBoard { board: board, symmetry: symmetry }
+    }
+
+    pub fn size(&self) -> u8 {
+        self.board.size()
+    }
+
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    pub fn color(&self, c: &Coord) -> Color {
+        let original = c.transform(self.symmetry.inverse(), self.size());
+        self.board.color(&original)
+    }
+}
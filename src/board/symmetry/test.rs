@@ -0,0 +1,74 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Empty;
+use board::Pass;
+use board::Play;
+use board::White;
+use ruleset::Minimal;
+use super::Rotate90;
+use super::Symmetry;
+use super::TransformedBoard;
+
+#[test]
+fn there_are_8_different_images_of_an_off_axis_coord() {
+    let c = Coord::new(3, 1);
+    let mut images: Vec<Coord> = Symmetry::all().iter().map(|&s| c.transform(s, 9)).collect();
+    images.sort();
+    images.dedup();
+    assert_eq!(8, images.len());
+}
+
+#[test]
+fn the_center_is_its_own_image() {
+    let c = Coord::new(5, 5);
+    assert!(Symmetry::all().iter().all(|&s| c.transform(s, 9) == c));
+}
+
+#[test]
+fn the_inverse_undoes_the_transformation() {
+    for &s in Symmetry::all().iter() {
+        for &c in Coord::for_board_size(5).iter() {
+            assert_eq!(c, c.transform(s, 5).transform(s.inverse(), 5));
+        }
+    }
+}
+
+#[test]
+fn rotate_90_moves_the_lower_left_corner_to_the_lower_right() {
+    assert_eq!(Coord::new(9, 1), Coord::new(1, 1).transform(Rotate90, 9));
+    assert_eq!(Play(Black, 9, 1), Play(Black, 1, 1).transform(Rotate90, 9));
+    assert_eq!(Pass(White), Pass(White).transform(Rotate90, 9));
+}
+
+#[test]
+fn a_transformed_board_shows_the_stones_at_their_images() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 1, 1));
+    let view = TransformedBoard::new(&b, Rotate90);
+    assert_eq!(Black, view.color(&Coord::new(9, 1)));
+    assert_eq!(Empty, view.color(&Coord::new(1, 1)));
+}
@@ -0,0 +1,201 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Coord;
+use board::Move;
+use board::Pass;
+use board::Play;
+use board::White;
+use game::Game;
+use ruleset::AnySizeTrompTaylor;
+use ruleset::KgsChinese;
+use ruleset::Ruleset;
+use score::Score;
+use super::naive::NaiveBoard;
+
+use std::collections::HashSet;
+use std::rand::Rng;
+use std::rand::SeedableRng;
+use std::rand::XorShiftRng;
+
+// Moves are played by whoever is next, so that every subsequence of
+// a game is a game, too. That's what makes shrinking work.
+fn recolor(m: &Move, game: &Game) -> Move {
+    let color = game.next_player();
+    match *m {
+        Pass(_)           => Pass(color),
+        Play(_, col, row) => Play(color, col, row)
+    }
+}
+
+// Replays the moves on a game and on the naive board. Returns the
+// number of moves up to and including the first difference between
+// them, and a description of the difference.
+fn first_difference(size: u8, ruleset: Ruleset, moves: &Vec<Move>) -> Option<(usize, String)> {
+    let mut game = Game::new(size, 6.5, ruleset);
+    let mut naive = NaiveBoard::new(size, ruleset);
+    for (i, m) in moves.iter().enumerate() {
+        let m = recolor(m, &game);
+        let expected = naive.play(m);
+        match game.play(m) {
+            Ok(g) => {
+                if expected.is_err() {
+                    return Some((i + 1, format!("{:?} is legal but should be {:?}", m, expected)));
+                }
+                game = g;
+            },
+            Err(e) => {
+                let actual = Err(e);
+                if actual != expected {
+                    return Some((i + 1, format!("{:?} is {:?} but should be {:?}", m, actual, expected)));
+                }
+                continue;
+            }
+        }
+        match compare(&game, &naive, &m) {
+            Some(difference) => return Some((i + 1, format!("After {:?}: {}", m, difference))),
+            None             => {}
+        }
+    }
+    None
+}
+
+fn compare(game: &Game, naive: &NaiveBoard, m: &Move) -> Option<String> {
+    let board = game.board();
+    for c in Coord::for_board_size(game.size()).iter() {
+        if board.color(c) != naive.color(c) {
+            return Some(format!("{:?} is {:?} but should be {:?}", c, board.color(c), naive.color(c)));
+        }
+    }
+    let legal: HashSet<Move> = game.legal_moves().into_iter().collect();
+    let expected_legal: HashSet<Move> = naive.legal_moves().into_iter().collect();
+    if legal != expected_legal {
+        return Some(format!("The legal moves are {:?} but should be {:?}", legal, expected_legal));
+    }
+    if !m.is_pass() {
+        let captured: HashSet<Coord> = board.adv_stones_removed().iter().cloned().collect();
+        let expected_captured: HashSet<Coord> = naive.captured().iter().cloned().collect();
+        if captured != expected_captured {
+            return Some(format!("The captured stones are {:?} but should be {:?}", captured, expected_captured));
+        }
+    }
+    if board.ko() != naive.ko() {
+        return Some(format!("The ko is {:?} but should be {:?}", board.ko(), naive.ko()));
+    }
+    if game.is_over() != naive.is_game_over() {
+        return Some(format!("The game being over is {} but should be {}", game.is_over(), naive.is_game_over()));
+    }
+    let hash = naive.hash(game.zobrist_table());
+    if game.hash() != hash {
+        return Some(format!("The hash is {} but should be {}", game.hash(), hash));
+    }
+    let score = Score::new(&board);
+    let expected_score = naive.area_score();
+    if (score.black_stones(), score.white_stones()) != expected_score {
+        return Some(format!("The score is {:?} but should be {:?}", (score.black_stones(), score.white_stones()), expected_score));
+    }
+    None
+}
+
+// Removes moves from the sequence as long as it still fails.
+fn shrink<F: Fn(&Vec<Move>) -> Option<usize>>(moves: &Vec<Move>, fails: F) -> Vec<Move> {
+    let mut shortest = moves.clone();
+    match fails(&shortest) {
+        Some(length) => shortest.truncate(length),
+        None         => return shortest
+    }
+    let mut i = 0;
+    while i < shortest.len() {
+        let mut candidate = shortest.clone();
+        candidate.remove(i);
+        match fails(&candidate) {
+            Some(length) => {
+                candidate.truncate(length);
+                shortest = candidate;
+            },
+            None => i += 1
+        }
+    }
+    shortest
+}
+
+// Plays random games on the naive board, so that every move in the
+// sequence is legal there.
+fn random_game<R: Rng>(rng: &mut R, size: u8, ruleset: Ruleset) -> Vec<Move> {
+    let mut naive = NaiveBoard::new(size, ruleset);
+    let mut moves = Vec::new();
+    let max_moves = size as usize * size as usize * 3;
+    while !naive.is_game_over() && moves.len() < max_moves {
+        let legal = naive.legal_moves();
+        let m = legal[rng.gen_range(0, legal.len())];
+        if naive.play(m).is_ok() {
+            moves.push(m);
+        }
+    }
+    moves
+}
+
+fn check_random_games(games: usize, size: u8, ruleset: Ruleset, seed: [u32; 4]) {
+    let mut rng: XorShiftRng = SeedableRng::from_seed(seed);
+    for _ in range(0, games) {
+        let moves = random_game(&mut rng, size, ruleset);
+        match first_difference(size, ruleset, &moves) {
+            Some(_) => {
+                let minimal = shrink(&moves, |ms| first_difference(size, ruleset, ms).map(|(length, _)| length));
+                let (_, difference) = first_difference(size, ruleset, &minimal).unwrap();
+                let gtp: Vec<String> = minimal.iter().map(|m| m.to_gtp()).collect();
+                panic!("{:?} on {}x{}: {}\nMinimal move sequence: {}", ruleset, size, size, difference, gtp.connect(" "));
+            },
+            None => {}
+        }
+    }
+}
+
+#[test]
+fn the_board_agrees_with_the_naive_board_in_random_games_with_suicide() {
+    check_random_games(500, 5, AnySizeTrompTaylor, [1, 1, 2, 3]);
+    check_random_games(100, 7, AnySizeTrompTaylor, [5, 8, 13, 21]);
+}
+
+#[test]
+fn the_board_agrees_with_the_naive_board_in_random_games_without_suicide() {
+    check_random_games(500, 5, KgsChinese, [34, 55, 89, 144]);
+    check_random_games(100, 7, KgsChinese, [233, 377, 610, 987]);
+}
+
+#[test]
+fn shrinking_keeps_only_the_moves_that_cause_the_failure() {
+    let moves = vec!(Play(Black, 1, 1), Play(White, 2, 2), Play(Black, 3, 3),
+                     Play(White, 4, 4), Play(Black, 5, 5));
+    let fails = |ms: &Vec<Move>| {
+        let has_2_2 = ms.iter().any(|m| m.coord() == Coord::new(2, 2));
+        match ms.iter().position(|m| m.coord() == Coord::new(4, 4)) {
+            Some(i) if has_2_2 => Some(i + 1),
+            _                  => None
+        }
+    };
+    let minimal = shrink(&moves, fails);
+    let coords: Vec<Coord> = minimal.iter().map(|m| m.coord()).collect();
+    assert_eq!(vec!(Coord::new(2, 2), Coord::new(4, 4)), coords);
+}
@@ -24,10 +24,14 @@
 
 use board::Black;
 use board::Board;
+use board::Coord;
+use board::Empty;
 use board::IllegalMove;
+use board::Pass;
 use board::Play;
 use board::White;
 use ruleset::AnySizeTrompTaylor;
+use ruleset::Minimal;
 use sgf::Parser;
 
 #[test]
@@ -49,6 +53,24 @@ fn replaying_directly_on_a_ko_point_should_be_illegal() {
 
 }
 
+#[test]
+fn passing_lifts_the_ko_ban() {
+    let mut b = Board::new(19, 6.5, AnySizeTrompTaylor);
+
+    b.play(Play(Black, 4, 4));
+    b.play(Play(White, 5, 4));
+    b.play(Play(Black, 3, 3));
+    b.play(Play(White, 4, 3));
+    b.play(Play(Black, 3, 5));
+    b.play(Play(White, 4, 5));
+    b.play(Play(Black, 2, 4));
+    b.play(Play(White, 3, 4));
+    b.play(Pass(Black));
+
+    assert_eq!(b.ko(), None);
+    assert!(b.play(Play(White, 4, 4)).is_ok());
+}
+
 #[test]
 fn positional_super_ko_should_be_legal() {
     let parser   = Parser::from_path(Path::new("fixtures/sgf/positional-superko.sgf"));
@@ -57,3 +79,41 @@ fn positional_super_ko_should_be_legal() {
     let super_ko = board.play(Play(White, 2, 9));
     assert_eq!(super_ko.is_ok(), true);
 }
+
+#[test]
+fn retaking_a_snapback_should_be_legal() {
+    let mut b = Board::new(5, 6.5, Minimal);
+
+    b.play(Play(White, 1, 1));
+    b.play(Play(White, 1, 3));
+    b.play(Play(White, 2, 3));
+    b.play(Play(White, 3, 2));
+    b.play(Play(White, 3, 1));
+    b.play(Play(Black, 2, 1));
+    b.play(Play(Black, 2, 2));
+    b.play(Play(Black, 1, 2));
+
+    assert_eq!(b.ko(), None);
+    assert!(b.play(Play(White, 1, 1)).is_ok());
+    assert_eq!(b.color(&Coord::new(2, 2)), Empty);
+}
+
+#[test]
+fn a_tenuki_lifts_the_ko_ban_without_ending_the_game() {
+    let mut b = Board::new(19, 6.5, AnySizeTrompTaylor);
+
+    b.play(Play(Black, 4, 4));
+    b.play(Play(White, 5, 4));
+    b.play(Play(Black, 3, 3));
+    b.play(Play(White, 4, 3));
+    b.play(Play(Black, 3, 5));
+    b.play(Play(White, 4, 5));
+    b.play(Play(Black, 2, 4));
+    b.play(Play(White, 3, 4));
+    b.tenuki();
+    b.tenuki();
+
+    assert_eq!(b.ko(), None);
+    assert!(!b.is_game_over());
+    assert!(b.play(Play(Black, 4, 4)).is_ok());
+}
@@ -23,6 +23,7 @@
 
 use board::Black;
 use board::Board;
+use board::Color;
 use board::Coord;
 use board::Empty;
 use board::IllegalMove;
@@ -34,9 +35,20 @@ use ruleset::AnySizeTrompTaylor;
 use ruleset::KgsChinese;
 use ruleset::Minimal;
 
+use std::rand::Rng;
+use std::rand::SeedableRng;
+use std::rand::XorShiftRng;
 use test::Bencher;
 
+mod differential;
 mod ko;
+mod naive;
+
+#[test]
+fn colors_can_be_converted_to_gtp_and_back() {
+    assert_eq!(Color::from_gtp(Black.to_gtp().as_slice()), Black);
+    assert_eq!(Color::from_gtp(White.to_gtp().as_slice()), White);
+}
 
 #[test]
 fn getting_a_valid_coord_returns_a_color() {
@@ -435,6 +447,16 @@ fn komi_returns_the_komi() {
     assert_eq!(b.komi(), 6.5);
 }
 
+#[test]
+fn last_move_returns_the_move_played_last() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    assert_eq!(None, b.last_move());
+    b.play(Play(Black, 3, 3));
+    assert_eq!(Some(Play(Black, 3, 3)), b.last_move());
+    b.play(Pass(White));
+    assert_eq!(Some(Pass(White)), b.last_move());
+}
+
 #[bench]
 fn bench_play_method(b: &mut Bencher) {
     b.iter(|| {
@@ -442,3 +464,39 @@ fn bench_play_method(b: &mut Bencher) {
         board.play(Play(Black, 14, 14)).unwrap();
     });
 }
+
+#[test]
+fn the_incremental_state_matches_the_stones_in_random_games() {
+    let mut rng: XorShiftRng = SeedableRng::from_seed([3, 1, 4, 1]);
+    for &ruleset in [AnySizeTrompTaylor, KgsChinese].iter() {
+        for &size in [5u8, 7, 9].iter() {
+            for _ in range(0us, 10) {
+                let mut b = Board::new(size, 6.5, ruleset);
+                let max_moves = size as usize * size as usize * 3;
+                let mut moves = 0;
+                while !b.is_game_over() && moves < max_moves {
+                    let legal = b.legal_moves();
+                    let m = legal[rng.gen_range(0, legal.len())];
+                    b.play(m).unwrap();
+                    match b.check_invariants() {
+                        Ok(_)  => {},
+                        Err(e) => panic!("{} after {:?}\n{}", e, m, b.as_string())
+                    }
+                    moves += 1;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn suicide_gives_the_liberties_back_to_the_surrounding_chains() {
+    let mut b = Board::new(5, 6.5, AnySizeTrompTaylor);
+    b.play(Play(Black, 2, 1));
+    b.play(Play(White, 5, 5));
+    b.play(Play(Black, 1, 2));
+    b.play(Play(White, 1, 1));
+    assert_eq!(Empty, b.color(&Coord::new(1, 1)));
+    assert_eq!(3, b.get_chain(Coord::new(2, 1)).unwrap().liberties().len());
+    assert!(b.check_invariants().is_ok());
+}
@@ -0,0 +1,283 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::IllegalMove;
+use board::Move;
+use board::Pass;
+use board::Play;
+use board::White;
+use game::ZobristHashTable;
+use ruleset::Minimal;
+use ruleset::Ruleset;
+
+// A board that recomputes the chains by flood fill on every move.
+// It's slow, but simple enough to be obviously correct, so that the
+// real board (and the superko check of the game) can be compared
+// against it.
+#[derive(Clone)]
+pub struct NaiveBoard {
+    captured:        Vec<Coord>,
+    history:         Vec<Vec<Color>>,
+    ko:              Option<Coord>,
+    passes:          usize,
+    previous_player: Color,
+    ruleset:         Ruleset,
+    size:            u8,
+    stones:          Vec<Color>
+}
+
+impl NaiveBoard {
+    pub fn new(size: u8, ruleset: Ruleset) -> NaiveBoard {
+        let stones: Vec<Color> = range(0, size as usize * size as usize).map(|_| Empty).collect();
+        NaiveBoard {
+            captured:        vec!(),
+            history:         vec!(stones.clone()),
+            ko:              None,
+            passes:          0,
+            previous_player: White,
+            ruleset:         ruleset,
+            size:            size,
+            stones:          stones
+        }
+    }
+
+    pub fn color(&self, c: &Coord) -> Color {
+        self.stones[c.to_index(self.size)]
+    }
+
+    pub fn ko(&self) -> Option<Coord> {
+        self.ko
+    }
+
+    // The opponent stones the last move captured.
+    pub fn captured(&self) -> &Vec<Coord> {
+        &self.captured
+    }
+
+    pub fn next_player(&self) -> Color {
+        self.previous_player.opposite()
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.passes >= 2
+    }
+
+    // The legal moves according to the rules of the board, i.e.
+    // without checking for superko.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let color = self.next_player();
+        let mut moves: Vec<Move> = Coord::for_board_size(self.size)
+            .iter()
+            .map(|c| Play(color, c.col, c.row))
+            .filter(|&m| self.is_legal(m).is_ok())
+            .collect();
+        moves.push(Pass(color));
+        moves
+    }
+
+    pub fn is_legal(&self, m: Move) -> Result<(), IllegalMove> {
+        self.after(m).map(|_| ())
+    }
+
+    // Plays the move like a game does, including the positional
+    // superko check.
+    pub fn play(&mut self, m: Move) -> Result<(), IllegalMove> {
+        let (stones, captured, ko) = try!(self.after(m));
+        if m.is_pass() {
+            self.passes += 1;
+        } else {
+            if self.history.contains(&stones) {
+                return Err(IllegalMove::SuperKo);
+            }
+            self.history.push(stones.clone());
+            self.passes = 0;
+        }
+        self.captured = captured;
+        self.ko = ko;
+        self.previous_player = *m.color();
+        self.stones = stones;
+        Ok(())
+    }
+
+    // The hash of the stones, using the keys of the table.
+    pub fn hash(&self, table: &ZobristHashTable) -> u64 {
+        let mut hash = table.init_hash();
+        for c in Coord::for_board_size(self.size).iter() {
+            match self.color(c) {
+                Empty => {},
+                color => hash = table.add_stone_to_hash(hash, &Play(color, c.col, c.row))
+            }
+        }
+        hash
+    }
+
+    // The area score with all stones alive: the stones plus the
+    // empty regions that only border stones of one color.
+    pub fn area_score(&self) -> (usize, usize) {
+        let mut black = self.stones.iter().filter(|&&c| c == Black).count();
+        let mut white = self.stones.iter().filter(|&&c| c == White).count();
+        let mut seen = Vec::new();
+        for start in Coord::for_board_size(self.size).into_iter() {
+            if self.color(&start) != Empty || seen.contains(&start) {
+                continue;
+            }
+            let mut region = vec!(start);
+            let mut borders = Vec::new();
+            let mut i = 0;
+            while i < region.len() {
+                let c = region[i];
+                for n in c.neighbours(self.size).into_iter() {
+                    match self.color(&n) {
+                        Empty => if !region.contains(&n) { region.push(n) },
+                        color => if !borders.contains(&color) { borders.push(color) }
+                    }
+                }
+                i += 1;
+            }
+            if borders == vec!(Black) {
+                black += region.len();
+            } else if borders == vec!(White) {
+                white += region.len();
+            }
+            seen.extend(region.into_iter());
+        }
+        (black, white)
+    }
+
+    // The stones, the captured stones and the ko point after the
+    // move.
+    fn after(&self, m: Move) -> Result<(Vec<Color>, Vec<Coord>, Option<Coord>), IllegalMove> {
+        if self.is_game_over() && !self.ruleset.game_over_play() {
+            return Err(IllegalMove::GameAlreadyOver);
+        }
+        if self.previous_player == *m.color() && !self.ruleset.same_player() {
+            return Err(IllegalMove::SamePlayerPlayedTwice);
+        }
+        if m.is_pass() {
+            return Ok((self.stones.clone(), vec!(), None));
+        }
+        let c = m.coord();
+        if !c.is_inside(self.size) {
+            return Err(IllegalMove::PlayOutOfBoard);
+        }
+        if self.color(&c) != Empty {
+            return Err(IllegalMove::IntersectionNotEmpty);
+        }
+        if self.ko == Some(c) {
+            return Err(IllegalMove::Ko);
+        }
+        let color = *m.color();
+        let mut stones = self.stones.clone();
+        stones[c.to_index(self.size)] = color;
+        let mut captured = Vec::new();
+        for n in c.neighbours(self.size).iter() {
+            if stones[n.to_index(self.size)] != color.opposite() {
+                continue;
+            }
+            let (chain, libs) = self.chain(&stones, n);
+            if libs == 0 {
+                for s in chain.into_iter() {
+                    if !captured.contains(&s) {
+                        captured.push(s);
+                    }
+                }
+            }
+        }
+        for s in captured.iter() {
+            stones[s.to_index(self.size)] = Empty;
+        }
+        let (own, libs) = self.chain(&stones, &c);
+        if libs == 0 {
+            if !self.ruleset.suicide_allowed() {
+                return Err(IllegalMove::SuicidePlay);
+            }
+            for s in own.iter() {
+                stones[s.to_index(self.size)] = Empty;
+            }
+            return Ok((stones, captured, None));
+        }
+        let ko = if captured.len() == 1 && own.len() == 1 && libs == 1 { Some(captured[0]) } else { None };
+        Ok((stones, captured, ko))
+    }
+
+    // The stones of the chain at the coord and its number of
+    // liberties.
+    fn chain(&self, stones: &Vec<Color>, start: &Coord) -> (Vec<Coord>, usize) {
+        let color = stones[start.to_index(self.size)];
+        let mut chain = vec!(*start);
+        let mut libs = Vec::new();
+        let mut i = 0;
+        while i < chain.len() {
+            let c = chain[i];
+            for n in c.neighbours(self.size).into_iter() {
+                let neighbour = stones[n.to_index(self.size)];
+                if neighbour == Empty && !libs.contains(&n) {
+                    libs.push(n);
+                } else if neighbour == color && !chain.contains(&n) {
+                    chain.push(n);
+                }
+            }
+            i += 1;
+        }
+        (chain, libs.len())
+    }
+}
+
+#[test]
+fn the_naive_board_captures_stones() {
+    let mut b = NaiveBoard::new(5, Minimal);
+    b.play(Play(Black, 1, 2)).unwrap();
+    b.play(Play(White, 1, 1)).unwrap();
+    b.play(Play(Black, 2, 1)).unwrap();
+    assert_eq!(Empty, b.color(&Coord::new(1, 1)));
+    assert_eq!(&vec!(Coord::new(1, 1)), b.captured());
+}
+
+#[test]
+fn the_naive_board_knows_about_ko() {
+    let mut b = NaiveBoard::new(5, Minimal);
+    for &m in [Play(Black, 2, 1), Play(Black, 1, 2), Play(Black, 2, 3), Play(White, 3, 1),
+               Play(White, 4, 2), Play(White, 3, 3), Play(White, 2, 2)].iter() {
+        b.play(m).unwrap();
+    }
+    b.play(Play(Black, 3, 2)).unwrap();
+    assert_eq!(Some(Coord::new(2, 2)), b.ko());
+    assert_eq!(Err(IllegalMove::Ko), b.play(Play(White, 2, 2)));
+}
+
+#[test]
+fn the_naive_board_counts_the_area() {
+    let mut b = NaiveBoard::new(5, Minimal);
+    for &m in [Play(Black, 2, 1), Play(White, 4, 1), Play(Black, 2, 2), Play(White, 4, 2),
+               Play(Black, 2, 3), Play(White, 4, 3), Play(Black, 2, 4), Play(White, 4, 4),
+               Play(Black, 2, 5), Play(White, 4, 5)].iter() {
+        b.play(m).unwrap();
+    }
+    assert_eq!((10, 10), b.area_score());
+    b.play(Play(Black, 3, 3)).unwrap();
+    assert_eq!((11, 10), b.area_score());
+}
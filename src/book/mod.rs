@@ -0,0 +1,202 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Move;
+use board::Play;
+use board::Symmetry;
+use game::ZobristHashTable;
+use rustc_serialize::json;
+use sgf::Parser;
+use sgf::collection::load;
+use sgf::collection::sgf_files;
+
+use std::collections::HashMap;
+use std::io::fs::File;
+
+mod test;
+
+// The seed of the hash table used for the keys of the book. Changing
+// it invalidates all existing books.
+static SEED: [u32; 4] = [0x1f2e3d4c, 0x5b6a7988, 0x97a6b5c4, 0xd3e2f1a0];
+
+// A move of the book in the canonical orientation of its position.
+// In a symmetric position all the moves that are equivalent under its
+// symmetries share the same book move.
+#[derive(Clone, Copy, Show, Eq, PartialEq, RustcEncodable, RustcDecodable)]
+pub struct BookMove {
+    col:   u8,
+    row:   u8,
+    count: usize,
+    wins:  usize
+}
+
+impl BookMove {
+    // How often the move was played in the games the book was built
+    // from.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // How often the player who played the move won the game.
+    pub fn wins(&self) -> usize {
+        self.wins
+    }
+}
+
+// The part of the book that gets saved.
+#[derive(RustcEncodable, RustcDecodable)]
+struct Entries {
+    size:      u8,
+    max_moves: usize,
+    positions: HashMap<String, Vec<BookMove>>
+}
+
+// The moves played in a collection of games, keyed by the canonical
+// hash of the position and the player to move. Positions that only
+// differ by a rotation or reflection share their moves.
+pub struct Book {
+    entries: Entries,
+    table:   ZobristHashTable
+}
+
+impl Book {
+    // A book for the given board size that contains only the first
+    // max_moves moves of each game.
+    pub fn new(size: u8, max_moves: usize) -> Book {
+        Book::with_entries(Entries { size: size, max_moves: max_moves, positions: HashMap::new() })
+    }
+
+    pub fn from_path(path: Path) -> Book {
+        let contents = File::open(&path).read_to_string().unwrap();
+        Book::with_entries(json::decode(contents.as_slice()).unwrap())
+    }
+
+    fn with_entries(entries: Entries) -> Book {
+        let table = ZobristHashTable::seeded(entries.size, SEED);
+        Book { entries: entries, table: table }
+    }
+
+    pub fn save(&self, path: Path) {
+        let mut file = File::create(&path).unwrap();
+        file.write_str(json::encode(&self.entries).as_slice()).unwrap();
+    }
+
+    pub fn size(&self) -> u8 {
+        self.entries.size
+    }
+
+    // The number of positions in the book.
+    pub fn len(&self) -> usize {
+        self.entries.positions.len()
+    }
+
+    // Adds the first moves of the game. Games played on another board
+    // size are skipped.
+    pub fn add_game(&mut self, parser: &Parser) {
+        let winner = parser.winner();
+        let mut game = match parser.initial_game() {
+            Ok(g)  => g,
+            Err(_) => return
+        };
+        if game.size() != self.size() {
+            return;
+        }
+        for &m in parser.moves().iter().take(self.entries.max_moves) {
+            self.add_position(&game.board(), &m, *m.color() == winner);
+            game = match game.play(m) {
+                Ok(g)  => g,
+                Err(_) => return
+            };
+        }
+    }
+
+    pub fn add_position(&mut self, board: &Board, m: &Move, won: bool) {
+        if m.is_pass() || board.size() != self.size() || board.next_player() != *m.color() {
+            return;
+        }
+        let (key, symmetries) = self.key(board);
+        let coord = symmetries.iter()
+            .map(|&s| m.coord().transform(s, board.size()))
+            .min_by(|c| (c.col, c.row))
+            .unwrap();
+        let wins = if won { 1 } else { 0 };
+        if !self.entries.positions.contains_key(&key) {
+            self.entries.positions.insert(key.clone(), Vec::new());
+        }
+        let moves = self.entries.positions.get_mut(&key).unwrap();
+        match moves.iter_mut().find(|bm| bm.col == coord.col && bm.row == coord.row) {
+            Some(bm) => {
+                bm.count += 1;
+                bm.wins += wins;
+                return;
+            },
+            None => {}
+        }
+        moves.push(BookMove { col: coord.col, row: coord.row, count: 1, wins: wins });
+    }
+
+    // The book moves for the player to move, the most played first.
+    pub fn moves(&self, board: &Board) -> Vec<(Move, BookMove)> {
+        if board.size() != self.size() {
+            return vec!();
+        }
+        let (key, symmetries) = self.key(board);
+        let color = board.next_player();
+        let mut moves: Vec<(Move, BookMove)> = match self.entries.positions.get(&key) {
+            Some(moves) => moves.iter().map(|&bm| {
+                let coord = Coord::new(bm.col, bm.row).transform(symmetries[0].inverse(), board.size());
+                (Play(color, coord.col, coord.row), bm)
+            }).collect(),
+            None => vec!()
+        };
+        moves.sort_by(|&(_, a), &(_, b)| (b.count, b.wins).cmp(&(a.count, a.wins)));
+        moves
+    }
+
+    // The most played move in the position, if it's in the book.
+    pub fn lookup(&self, board: &Board) -> Option<Move> {
+        self.moves(board).first().map(|&(m, _)| m)
+    }
+
+    fn key(&self, board: &Board) -> (String, Vec<Symmetry>) {
+        let (hash, symmetries) = self.table.canonical(board);
+        let player = if board.next_player() == Black { "b" } else { "w" };
+        (format!("{:016x}{}", hash, player), symmetries)
+    }
+}
+
+// Builds a book from all the SGF files of the given board size in
+// the directory and writes it to the output file.
+pub fn build(directory: Path, output: Path, size: u8, max_moves: usize) {
+    let mut book = Book::new(size, max_moves);
+    for path in sgf_files(&directory).iter() {
+        println!("Reading {}", path.display());
+        match load(path) {
+            Some(parser) => book.add_game(&parser),
+            None         => {}
+        }
+    }
+    println!("{} positions in the book", book.len());
+    book.save(output);
+}
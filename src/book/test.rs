@@ -0,0 +1,134 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Play;
+use board::White;
+use engine::BookEngine;
+use engine::Engine;
+use engine::RandomEngine;
+use game::Game;
+use ruleset::Minimal;
+use sgf::Parser;
+use super::Book;
+
+use std::io::TempDir;
+
+fn book() -> Book {
+    let mut book = Book::new(19, 30);
+    book.add_game(&Parser::from_path(Path::new("fixtures/sgf/opening.sgf")));
+    book
+}
+
+#[test]
+fn add_game_adds_a_position_per_move() {
+    assert_eq!(4, book().len());
+}
+
+#[test]
+fn add_game_only_adds_the_first_moves() {
+    let mut book = Book::new(19, 2);
+    book.add_game(&Parser::from_path(Path::new("fixtures/sgf/opening.sgf")));
+    assert_eq!(2, book.len());
+}
+
+#[test]
+fn games_of_another_size_are_skipped() {
+    let mut book = Book::new(9, 30);
+    book.add_game(&Parser::from_path(Path::new("fixtures/sgf/opening.sgf")));
+    assert_eq!(0, book.len());
+}
+
+// The game starts on Q16, but on the empty board all four 4-4
+// points are the same move.
+#[test]
+fn lookup_returns_the_move_of_the_game() {
+    let b = Board::new(19, 6.5, Minimal);
+    assert_eq!(Some(Play(Black, 4, 4)), book().lookup(&b));
+}
+
+#[test]
+fn equivalent_moves_of_a_symmetric_position_are_merged() {
+    let b = Board::new(19, 6.5, Minimal);
+    let mut book = Book::new(19, 30);
+    book.add_position(&b, &Play(Black, 16, 16), true);
+    book.add_position(&b, &Play(Black, 4, 16), false);
+    let moves = book.moves(&b);
+    assert_eq!(1, moves.len());
+    let (_, bm) = moves[0];
+    assert_eq!(2, bm.count());
+    assert_eq!(1, bm.wins());
+}
+
+#[test]
+fn lookup_finds_symmetric_positions() {
+    let mut b = Board::new(19, 6.5, Minimal);
+    b.play(Play(Black, 4, 16));
+    assert_eq!(Some(Play(White, 16, 4)), book().lookup(&b));
+}
+
+#[test]
+fn lookup_returns_none_for_unknown_positions() {
+    let mut b = Board::new(19, 6.5, Minimal);
+    b.play(Play(Black, 10, 10));
+    assert_eq!(None, book().lookup(&b));
+}
+
+#[test]
+fn moves_count_the_wins_of_the_player() {
+    let mut b = Board::new(19, 6.5, Minimal);
+    let book = book();
+    let (_, black) = book.moves(&b)[0];
+    assert_eq!(1, black.count());
+    assert_eq!(1, black.wins());
+    b.play(Play(Black, 16, 16));
+    let (_, white) = book.moves(&b)[0];
+    assert_eq!(1, white.count());
+    assert_eq!(0, white.wins());
+}
+
+#[test]
+fn a_saved_book_can_be_loaded_again() {
+    let dir = TempDir::new("book").unwrap();
+    let path = dir.path().join("book.json");
+    book().save(path.clone());
+    let loaded = Book::from_path(path);
+    let b = Board::new(19, 6.5, Minimal);
+    assert_eq!(4, loaded.len());
+    assert_eq!(Some(Play(Black, 4, 4)), loaded.lookup(&b));
+}
+
+#[test]
+fn the_book_engine_plays_book_moves() {
+    let engine = BookEngine::new(book(), Box::new(RandomEngine::new()));
+    let game = Game::new(19, 6.5, Minimal);
+    assert_eq!(Play(Black, 4, 4), engine.gen_move(Black, &game));
+}
+
+#[test]
+fn the_book_engine_asks_the_engine_out_of_book() {
+    let engine = BookEngine::new(book(), Box::new(RandomEngine::new()));
+    let game = Game::new(9, 6.5, Minimal);
+    assert!(game.play(engine.gen_move(Black, &game)).is_ok());
+}
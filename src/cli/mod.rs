@@ -26,6 +26,7 @@ use board::Pass;
 use board::Play;
 use game::Game;
 use ruleset::KgsChinese;
+use score::Influence;
 
 use std::io::stdio::stdin;
 
@@ -87,6 +88,7 @@ impl Driver {
             println!("");
             println!("{}", g);
             g.show_chains();
+            println!("Current estimate: {}", Influence::new(&g.board()).estimate(g.komi()));
         }
 
     }
@@ -0,0 +1,84 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Color;
+use board::Move;
+use book::Book;
+use engine::Engine;
+use game::Game;
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+// Plays the moves of the opening book as long as the position is in
+// the book and asks the wrapped engine otherwise.
+pub struct BookEngine<'a> {
+    book:   Book,
+    engine: Box<Engine + 'a>
+}
+
+impl<'a> BookEngine<'a> {
+    pub fn new(book: Book, engine: Box<Engine + 'a>) -> BookEngine<'a> {
+        BookEngine { book: book, engine: engine }
+    }
+
+    fn book_moves(&self, color: Color, game: &Game) -> Vec<Move> {
+        let board = game.board();
+        if board.next_player() != color {
+            return vec!();
+        }
+        self.book.moves(&board)
+            .into_iter()
+            .map(|(m, _)| m)
+            .filter(|m| game.play(*m).is_ok())
+            .collect()
+    }
+}
+
+impl<'a> Engine for BookEngine<'a> {
+    fn gen_move(&self, color: Color, game: &Game) -> Move {
+        match self.book_moves(color, game).first() {
+            Some(&m) => m,
+            None     => self.engine.gen_move(color, game)
+        }
+    }
+
+    fn ranked_moves(&self, color: Color, game: &Game) -> Vec<Move> {
+        let moves = self.book_moves(color, game);
+        if moves.len() > 0 {
+            moves
+        } else {
+            self.engine.ranked_moves(color, game)
+        }
+    }
+
+    fn win_rates(&self, color: Color, game: &Game) -> Vec<(Move, f32)> {
+        self.engine.win_rates(color, game)
+    }
+
+    fn set_interrupt(&mut self, interrupted: Arc<AtomicBool>) {
+        self.engine.set_interrupt(interrupted);
+    }
+
+    fn set_live_gfx(&mut self, live_gfx: bool) {
+        self.engine.set_live_gfx(live_gfx);
+    }
+}
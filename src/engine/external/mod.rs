@@ -0,0 +1,103 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Color;
+use board::Move;
+use board::Pass;
+use engine::Engine;
+use game::Game;
+use gtp::client::Client;
+use gtp::client::parse_move;
+
+use std::cell::RefCell;
+
+mod test;
+
+// The position the external engine currently knows about.
+struct Position {
+    komi:  f32,
+    moves: Vec<Move>,
+    size:  u8
+}
+
+// Uses an external GTP program as an engine. Before every genmove
+// the program gets told about the moves it hasn't seen yet, or the
+// whole game if it diverged from what it knows.
+pub struct ExternalGtpEngine {
+    client:   RefCell<Client>,
+    position: RefCell<Option<Position>>
+}
+
+impl ExternalGtpEngine {
+    pub fn new(client: Client) -> ExternalGtpEngine {
+        ExternalGtpEngine {
+            client:   RefCell::new(client),
+            position: RefCell::new(None)
+        }
+    }
+
+    pub fn spawn(command: &str) -> Result<ExternalGtpEngine, String> {
+        Client::spawn(command).map(|client| ExternalGtpEngine::new(client))
+    }
+
+    fn sync(&self, game: &Game) -> Result<(), String> {
+        let mut client = self.client.borrow_mut();
+        let mut position = self.position.borrow_mut();
+        let known = match *position {
+            Some(ref p) if p.size == game.size() && p.komi == game.komi() && game.moves().starts_with(p.moves.as_slice()) => p.moves.len(),
+            _ => {
+                try!(client.send(format!("boardsize {}", game.size()).as_slice()));
+                try!(client.send("clear_board"));
+                try!(client.send(format!("komi {}", game.komi()).as_slice()));
+                0
+            }
+        };
+        for m in game.moves().slice_from(known).iter() {
+            try!(client.send(format!("play {} {}", m.color().to_gtp(), m.to_gtp()).as_slice()));
+        }
+        *position = Some(Position { komi: game.komi(), moves: game.moves().clone(), size: game.size() });
+        Ok(())
+    }
+}
+
+impl Engine for ExternalGtpEngine {
+    // The Engine trait has no way to resign or fail, so we pass
+    // instead.
+    fn gen_move(&self, color: Color, game: &Game) -> Move {
+        if self.sync(game).is_err() {
+            *self.position.borrow_mut() = None;
+            return Pass(color);
+        }
+        let response = self.client.borrow_mut().send(format!("genmove {}", color.to_gtp()).as_slice());
+        let m = response.ok().and_then(|vertex| parse_move(color, vertex.as_slice(), game.size()));
+        let mut position = self.position.borrow_mut();
+        match m {
+            Some(m) => {
+                position.as_mut().map(|p| p.moves.push(m));
+                m
+            },
+            None => {
+                *position = None;
+                Pass(color)
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Pass;
+use board::Play;
+use board::White;
+use engine::Engine;
+use game::Game;
+use ruleset::KgsChinese;
+use super::ExternalGtpEngine;
+
+use std::io::TempDir;
+use std::io::fs::File;
+
+fn standin(vertex: &str, log: &Path) -> ExternalGtpEngine {
+    ExternalGtpEngine::spawn(format!("sh fixtures/gtp/standin.sh {} {}", vertex, log.display()).as_slice()).unwrap()
+}
+
+fn commands(log: &Path) -> Vec<String> {
+    let content = File::open(log).read_to_string().unwrap();
+    content.as_slice().lines().map(|line| String::from_str(line.trim())).collect()
+}
+
+fn count(commands: &Vec<String>, command: &str) -> usize {
+    commands.iter().filter(|c| c.as_slice() == command).count()
+}
+
+#[test]
+fn gen_move_returns_the_move_of_the_external_engine() {
+    let dir = TempDir::new("external").unwrap();
+    let engine = standin("d4", &dir.path().join("commands.log"));
+    let game = Game::new(9, 6.5, KgsChinese);
+    assert_eq!(Play(Black, 4, 4), engine.gen_move(Black, &game));
+}
+
+#[test]
+fn resigning_or_invalid_moves_become_passes() {
+    let dir = TempDir::new("external").unwrap();
+    let game = Game::new(9, 6.5, KgsChinese);
+    let engine = standin("resign", &dir.path().join("resign.log"));
+    assert_eq!(Pass(Black), engine.gen_move(Black, &game));
+    let engine = standin("z99", &dir.path().join("invalid.log"));
+    assert_eq!(Pass(Black), engine.gen_move(Black, &game));
+}
+
+#[test]
+fn only_new_moves_are_sent_to_the_external_engine() {
+    let dir = TempDir::new("external").unwrap();
+    let log = dir.path().join("commands.log");
+    let engine = standin("d4", &log);
+    let game = Game::new(9, 6.5, KgsChinese);
+    let game = game.play(Play(Black, 3, 3)).unwrap();
+    let m = engine.gen_move(White, &game);
+    let game = game.play(m).unwrap();
+    let game = game.play(Play(Black, 5, 5)).unwrap();
+    engine.gen_move(White, &game);
+    let commands = commands(&log);
+    assert_eq!(1, count(&commands, "clear_board"));
+    assert_eq!(1, count(&commands, "play b C3"));
+    assert_eq!(0, count(&commands, "play w D4"));
+    assert_eq!(1, count(&commands, "play b E5"));
+    assert_eq!(2, count(&commands, "genmove w"));
+}
+
+#[test]
+fn a_different_game_is_sent_from_scratch() {
+    let dir = TempDir::new("external").unwrap();
+    let log = dir.path().join("commands.log");
+    let engine = standin("d4", &log);
+    let game = Game::new(9, 6.5, KgsChinese);
+    engine.gen_move(Black, &game.play(Play(Black, 3, 3)).unwrap());
+    engine.gen_move(Black, &game.play(Play(Black, 5, 5)).unwrap());
+    engine.gen_move(Black, &Game::new(13, 6.5, KgsChinese));
+    let commands = commands(&log);
+    assert_eq!(3, count(&commands, "clear_board"));
+    assert_eq!(1, count(&commands, "boardsize 13"));
+}
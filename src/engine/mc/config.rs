@@ -0,0 +1,176 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use std::io::fs::File;
+
+// The knobs of the Monte Carlo search.
+#[derive(Clone, Copy, Show)]
+pub struct McConfig {
+    // Number of playouts per candidate move at the root
+    pub playouts:             usize,
+    // Weight of the UCB exploration term
+    pub exploration:          f32,
+    // Number of playouts after which the direct results and the
+    // AMAF results of a move are weighted equally
+    pub rave_equivalence:     f32,
+    // Number of virtual playouts the heuristic prior of a move is
+    // worth
+    pub prior_plays:          f32,
+    // Weight of the progressive bias towards moves with a good prior
+    pub bias_weight:          f32,
+    // Number of candidate moves considered from the start
+    pub widening_start:       usize,
+    // Number of playouts before the next candidate gets added
+    pub widening_plays:       f32,
+    // Growth of the number of playouts between two new candidates
+    pub widening_factor:      f32,
+    // How much each of the heuristics changes the prior of a move.
+    // Their sum is clamped to [0, 1], with 0.5 being a neutral move.
+    pub prior_capture:        f32,
+    pub prior_atari_escape:   f32,
+    pub prior_pattern:        f32,
+    pub prior_near_last:      f32,
+    pub prior_first_line:     f32,
+    pub prior_second_line:    f32,
+    // Probability that a playout plays one of the pattern moves
+    // around the last move, if there are any
+    pub playout_pattern_rate: f32,
+    // Weight a pattern needs to be played in a playout
+    pub playout_pattern_min:  f32,
+}
+
+impl McConfig {
+    pub fn new() -> McConfig {
+        McConfig {
+            playouts:             1000,
+            exploration:          0.2,
+            rave_equivalence:     1000.0,
+            prior_plays:          20.0,
+            bias_weight:          1.0,
+            widening_start:       5,
+            widening_plays:       40.0,
+            widening_factor:      1.4,
+            prior_capture:        0.3,
+            prior_atari_escape:   0.2,
+            prior_pattern:        0.15,
+            prior_near_last:      0.1,
+            prior_first_line:     -0.2,
+            prior_second_line:    -0.05,
+            playout_pattern_rate: 1.0,
+            playout_pattern_min:  1.0,
+        }
+    }
+
+    // Reads a file with one "name value" pair per line, as written
+    // by save. Knobs that aren't in the file keep their defaults.
+    pub fn from_path(path: Path) -> McConfig {
+        let contents = File::open(&path).read_to_string().unwrap();
+        let mut config = McConfig::new();
+        for line in contents.as_slice().lines() {
+            let parts: Vec<&str> = line.split(' ').collect();
+            if parts.len() != 2 {
+                continue;
+            }
+            match parts[1].parse::<f32>() {
+                Some(value) => config.set(parts[0], value),
+                None        => panic!("Invalid value for {}: {}", parts[0], parts[1])
+            }
+        }
+        match config.validate() {
+            Ok(())   => config,
+            Err(msg) => panic!("Invalid configuration in {}: {}", path.display(), msg)
+        }
+    }
+
+    // The number of playouts between two new candidates has to grow,
+    // otherwise the progressive widening never stops adding them.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.widening_plays >= 1.0) {
+            Err(format!("widening_plays has to be at least 1, not {}", self.widening_plays))
+        } else if !(self.widening_factor > 1.0) {
+            Err(format!("widening_factor has to be bigger than 1, not {}", self.widening_factor))
+        } else if !(self.playout_pattern_rate >= 0.0 && self.playout_pattern_rate <= 1.0) {
+            Err(format!("playout_pattern_rate has to be between 0 and 1, not {}", self.playout_pattern_rate))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn save(&self, path: Path) {
+        let lines: Vec<String> = McConfig::names()
+            .iter()
+            .map(|name| format!("{} {}", name, self.get(*name)))
+            .collect();
+        let mut file = File::create(&path).unwrap();
+        file.write_str(format!("{}\n", lines.connect("\n")).as_slice()).unwrap();
+    }
+
+    pub fn names() -> Vec<&'static str> {
+        vec!("playouts", "exploration", "rave_equivalence", "prior_plays", "bias_weight",
+             "widening_start", "widening_plays", "widening_factor",
+             "prior_capture", "prior_atari_escape", "prior_pattern", "prior_near_last",
+             "prior_first_line", "prior_second_line", "playout_pattern_rate", "playout_pattern_min")
+    }
+
+    pub fn get(&self, name: &str) -> f32 {
+        match name {
+            "playouts"             => self.playouts as f32,
+            "exploration"          => self.exploration,
+            "rave_equivalence"     => self.rave_equivalence,
+            "prior_plays"          => self.prior_plays,
+            "bias_weight"          => self.bias_weight,
+            "widening_start"       => self.widening_start as f32,
+            "widening_plays"       => self.widening_plays,
+            "widening_factor"      => self.widening_factor,
+            "prior_capture"        => self.prior_capture,
+            "prior_atari_escape"   => self.prior_atari_escape,
+            "prior_pattern"        => self.prior_pattern,
+            "prior_near_last"      => self.prior_near_last,
+            "prior_first_line"     => self.prior_first_line,
+            "prior_second_line"    => self.prior_second_line,
+            "playout_pattern_rate" => self.playout_pattern_rate,
+            "playout_pattern_min"  => self.playout_pattern_min,
+            _                      => panic!("Unknown parameter: {}", name)
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: f32) {
+        match name {
+            "playouts"             => self.playouts = value as usize,
+            "exploration"          => self.exploration = value,
+            "rave_equivalence"     => self.rave_equivalence = value,
+            "prior_plays"          => self.prior_plays = value,
+            "bias_weight"          => self.bias_weight = value,
+            "widening_start"       => self.widening_start = value as usize,
+            "widening_plays"       => self.widening_plays = value,
+            "widening_factor"      => self.widening_factor = value,
+            "prior_capture"        => self.prior_capture = value,
+            "prior_atari_escape"   => self.prior_atari_escape = value,
+            "prior_pattern"        => self.prior_pattern = value,
+            "prior_near_last"      => self.prior_near_last = value,
+            "prior_first_line"     => self.prior_first_line = value,
+            "prior_second_line"    => self.prior_second_line = value,
+            "playout_pattern_rate" => self.playout_pattern_rate = value,
+            "playout_pattern_min"  => self.playout_pattern_min = value,
+            _                      => panic!("Unknown parameter: {}", name)
+        }
+    }
+}
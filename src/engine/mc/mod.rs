@@ -20,26 +20,60 @@
  *                                                                      *
  ************************************************************************/
 
+pub use self::config::McConfig;
 use board::Color;
 use board::Move;
 use board::Pass;
 use game::Game;
+use patterns::Patterns;
 use playout::Playout;
 use super::Engine;
+use tactics::benson::PassAlive;
+use tactics::ladder;
+use tactics::semeai;
 
+use std::cell::RefCell;
+use std::cmp::Ordering::Equal;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::stdio::stderr;
+use std::num::Float;
+use std::rand::SeedableRng;
+use std::rand::XorShiftRng;
+use std::rand::random;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 
+mod config;
+mod prior;
 mod test;
 
+// Number of playouts between two gogui-gfx blocks.
+const LIVE_GFX_INTERVAL: usize = 500;
+// Number of moves of a playout that are shown after the best move.
+const VARIATION_LENGTH: usize = 5;
+
 #[derive(Copy)]
 struct MoveStats {
     wins: usize,
-    plays: usize
+    plays: usize,
+    rave_wins: usize,
+    rave_plays: usize,
+    prior: f32,
+    prior_plays: f32
 }
 
 impl MoveStats {
     pub fn new() -> MoveStats {
-        MoveStats { wins: 0, plays: 0 }
+        MoveStats { wins: 0, plays: 0, rave_wins: 0, rave_plays: 0, prior: 0.5, prior_plays: 0f32 }
+    }
+
+    // Starts the move off with prior_plays virtual playouts that
+    // were won with the probability given by the prior.
+    pub fn with_prior(prior: f32, prior_plays: f32) -> MoveStats {
+        MoveStats { prior: prior, prior_plays: prior_plays, ..MoveStats::new() }
     }
 
     pub fn won(&mut self) {
@@ -51,6 +85,17 @@ impl MoveStats {
         self.plays = self.plays + 1;
     }
 
+    // The move was played by the same player later on in a playout
+    // that was won (all moves as first).
+    pub fn rave_won(&mut self) {
+        self.rave_wins = self.rave_wins + 1;
+        self.rave_plays = self.rave_plays + 1;
+    }
+
+    pub fn rave_lost(&mut self) {
+        self.rave_plays = self.rave_plays + 1;
+    }
+
     pub fn all_wins(&self) -> bool {
         self.wins == self.plays
     }
@@ -66,52 +111,267 @@ impl MoveStats {
             (self.wins as f32) / (self.plays as f32)
         }
     }
+
+    pub fn rave_ratio(&self) -> f32 {
+        if self.rave_plays == 0 {
+            0f32
+        } else {
+            (self.rave_wins as f32) / (self.rave_plays as f32)
+        }
+    }
+
+    // The win ratio including the virtual playouts of the prior.
+    pub fn seeded_win_ratio(&self) -> f32 {
+        let plays = self.plays as f32 + self.prior_plays;
+        if plays == 0f32 {
+            0f32
+        } else {
+            (self.wins as f32 + self.prior * self.prior_plays) / plays
+        }
+    }
+
+    // Blends the win ratio with the AMAF win ratio. The weight of the
+    // AMAF results goes down as the move gets more playouts of its
+    // own, and reaches 1/2 after `equivalence` playouts.
+    pub fn value(&self, equivalence: f32) -> f32 {
+        if self.rave_plays == 0 || equivalence <= 0f32 {
+            return self.seeded_win_ratio();
+        }
+        let beta = (equivalence / (3f32 * self.plays as f32 + equivalence)).sqrt();
+        (1f32 - beta) * self.seeded_win_ratio() + beta * self.rave_ratio()
+    }
+
+    // A bonus for moves the heuristics like that fades away as the
+    // move gets more playouts.
+    pub fn progressive_bias(&self, weight: f32) -> f32 {
+        weight * self.prior / (self.plays as f32 + 1f32)
+    }
 }
 
-pub struct McEngine;
+pub struct McEngine {
+    config:      McConfig,
+    interrupted: Arc<AtomicBool>,
+    live_gfx:    bool,
+    patterns:    Rc<Patterns>,
+    rng:         RefCell<XorShiftRng>
+}
 
 impl McEngine {
     pub fn new() -> McEngine {
-        McEngine
+        McEngine::with_config(McConfig::new())
     }
 
-}
+    pub fn with_config(config: McConfig) -> McEngine {
+        McEngine::with_patterns(config, Patterns::mogo())
+    }
 
-impl Engine for McEngine {
-    fn gen_move(&self, color: Color, game: &Game) -> Move {
+    pub fn with_patterns(config: McConfig, patterns: Patterns) -> McEngine {
+        match config.validate() {
+            Ok(())   => {},
+            Err(msg) => panic!("Invalid configuration: {}", msg)
+        }
+        let rng = SeedableRng::from_seed([random(), random(), random(), random()]);
+        McEngine {
+            config:      config,
+            interrupted: Arc::new(AtomicBool::new(false)),
+            live_gfx:    false,
+            patterns:    Rc::new(patterns),
+            rng:         RefCell::new(rng)
+        }
+    }
+
+    // Makes the playouts reproducible.
+    pub fn seeded(self, seed: [u32; 4]) -> McEngine {
+        McEngine { rng: RefCell::new(SeedableRng::from_seed(seed)), ..self }
+    }
+
+    // The number of candidate moves, best prior first, that are
+    // considered after the given number of playouts. A new move gets
+    // added every time the number of playouts grows by the widening
+    // factor.
+    fn widened(&self, total_plays: usize) -> usize {
+        let mut count = self.config.widening_start;
+        let mut threshold = self.config.widening_plays;
+        while total_plays as f32 >= threshold {
+            count += 1;
+            threshold *= self.config.widening_factor;
+        }
+        count
+    }
+
+    // Picks the move to run the next playout for using UCB on the
+    // blended values plus the progressive bias. Only the first
+    // `count` moves are considered, and the ones without any
+    // playouts come first.
+    fn select(&self, moves: &Vec<Move>, count: usize, stats: &HashMap<Move, MoveStats>, total_plays: usize) -> Move {
+        let mut best = moves[0];
+        let mut best_value = -1f32;
+        for m in moves.iter().take(count) {
+            let ms = stats.get(m).unwrap();
+            if ms.plays == 0 {
+                return *m;
+            }
+            let exploration = ((total_plays as f32).ln() / ms.plays as f32).sqrt();
+            let value = ms.value(self.config.rave_equivalence)
+                + self.config.exploration * exploration
+                + ms.progressive_bias(self.config.bias_weight);
+            if value > best_value {
+                best = *m;
+                best_value = value;
+            }
+        }
+        best
+    }
+
+    // Runs the playouts for all candidate moves of the player.
+    fn search(&self, color: Color, game: &Game) -> HashMap<Move, MoveStats> {
         let mut stats = HashMap::new();
-        let moves = game.legal_moves();
+        let board = game.board();
+        let pass_alive = PassAlive::new(&board);
+        let mut moves: Vec<Move> = game.legal_moves()
+            .into_iter()
+            .filter(|m| m.is_pass() || !pass_alive.is_settled(&m.coord()))
+            .filter(|m| !ladder::is_wasted_move(&board, m))
+            .filter(|m| !semeai::is_losing_move(&board, m))
+            .collect();
         for m in moves.iter() {
-            stats.insert(m, MoveStats::new());
+            let value = prior::heuristic(&board, m, &*self.patterns, &self.config);
+            stats.insert(*m, MoveStats::with_prior(value, self.config.prior_plays));
         }
-        for m in moves.iter() {
-            for _ in range(0us, 1000) {
-                let g = game.play(*m).unwrap();
-                let playout = Playout::new(g.board());
-                let winner = playout.run();
-                let mut prev_move_stats = stats.get_mut(m).unwrap();
-                if winner == color {
+        moves.sort_by(|a, b| {
+            let prior_a = stats.get(a).unwrap().prior;
+            let prior_b = stats.get(b).unwrap().prior;
+            prior_b.partial_cmp(&prior_a).unwrap_or(Equal)
+        });
+        let mut variations = HashMap::new();
+        for i in range(0us, moves.len() * self.config.playouts) {
+            if self.interrupted.load(Ordering::SeqCst) {
+                break;
+            }
+            if self.live_gfx && i > 0 && i % LIVE_GFX_INTERVAL == 0 {
+                let _ = stderr().write_str(self.gfx(color, &stats, &variations, i).as_slice());
+            }
+            let count = self.widened(i);
+            let m = self.select(&moves, count, &stats, i);
+            let g = game.play(m).unwrap();
+            let playout = Playout::with_patterns(g.board(), self.patterns.clone(), self.config);
+            let result = playout.run_with_rng(&mut *self.rng.borrow_mut());
+            let won = result.winner() == color;
+            if self.live_gfx {
+                variations.insert(m, result.moves().iter().take(VARIATION_LENGTH).cloned().collect());
+            }
+            {
+                let mut prev_move_stats = stats.get_mut(&m).unwrap();
+                if won {
                     prev_move_stats.won();
                 } else {
                     prev_move_stats.lost();
                 }
             }
+            // Update the AMAF statistics of every candidate move the
+            // player played first on its intersection.
+            let mut seen = HashSet::new();
+            let own_moves = Some(m).into_iter()
+                .chain(result.moves().iter().cloned())
+                .filter(|pm| *pm.color() == color && !pm.is_pass());
+            for pm in own_moves {
+                if !seen.insert(pm.coord()) {
+                    continue;
+                }
+                match stats.get_mut(&pm) {
+                    Some(ms) => if won { ms.rave_won() } else { ms.rave_lost() },
+                    None     => {}
+                }
+            }
+        }
+        stats
+    }
+
+    // The move with the highest value among the moves that got
+    // playouts. Without any playouts (e.g. after an interrupt) the
+    // prior of the moves other than pass has to do.
+    fn best_move(&self, color: Color, stats: &HashMap<Move, MoveStats>) -> Move {
+        let searched = stats.values().any(|ms| ms.plays > 0);
+        let mut m = Pass(color);
+        let mut best_value = 0f32;
+        for (m_new, ms) in stats.iter().filter(|&(candidate, ms)| if searched { ms.plays > 0 } else { !candidate.is_pass() }) {
+            let value = if searched { ms.value(self.config.rave_equivalence) } else { ms.prior };
+            if value > best_value {
+                m = *m_new;
+                best_value = value;
+            }
+        }
+        m
+    }
+
+    // The gogui-gfx block that shows the progress of the search: the
+    // best move so far followed by the start of its latest playout,
+    // and the win rates of the searched moves.
+    fn gfx(&self, color: Color, stats: &HashMap<Move, MoveStats>, variations: &HashMap<Move, Vec<Move>>, playouts: usize) -> String {
+        let best = self.best_move(color, stats);
+        let mut variation = vec!(best);
+        match variations.get(&best) {
+            Some(moves) => variation.push_all(moves.as_slice()),
+            None        => {}
+        }
+        let var: Vec<String> = variation.iter()
+            .map(|m| format!("{} {}", m.color().to_gtp(), m.to_gtp()))
+            .collect();
+        let labels: Vec<String> = stats.iter()
+            .filter(|&(m, ms)| ms.plays > 0 && !m.is_pass())
+            .map(|(m, ms)| format!("{} {}", m.to_gtp(), (ms.win_ratio() * 100.0).round() as u32))
+            .collect();
+        let mut gfx = format!("gogui-gfx:\nVAR {}\n", var.connect(" "));
+        if labels.len() > 0 {
+            gfx.push_str(format!("LABEL {}\n", labels.connect(" ")).as_slice());
         }
+        gfx.push_str(format!("TEXT {} playouts, best {}\n\n", playouts, best.to_gtp()).as_slice());
+        gfx
+    }
+
+}
+
+impl Engine for McEngine {
+    fn gen_move(&self, color: Color, game: &Game) -> Move {
+        let stats = self.search(color, game);
         // pass if 0% wins
         // pass if 100% wins
-        if stats.iter().all(|(_, move_stats)| move_stats.all_wins() || move_stats.all_loses()) {
+        // but only judge the moves that got playouts, and never pass
+        // just because the search was interrupted
+        let searched: Vec<&MoveStats> = stats.values().filter(|ms| ms.plays > 0).collect();
+        let interrupted = self.interrupted.load(Ordering::SeqCst);
+        if !interrupted && searched.len() > 0 && searched.iter().all(|ms| ms.all_wins() || ms.all_loses()) {
             Pass(color)
         } else {
-            let mut m = Pass(color);
-            let mut move_stats = MoveStats::new();
-            for (m_new, ms) in stats.iter() {
-                if ms.win_ratio() > move_stats.win_ratio() {
-                    m = **m_new;
-                    move_stats = *ms;
-                }
-            }
-            m
+            self.best_move(color, &stats)
         }
     }
 
+    // The moves that got playouts, ordered by their value.
+    fn ranked_moves(&self, color: Color, game: &Game) -> Vec<Move> {
+        let stats = self.search(color, game);
+        let mut moves: Vec<(Move, f32)> = stats.iter()
+            .filter(|&(_, ms)| ms.plays > 0)
+            .map(|(m, ms)| (*m, ms.value(self.config.rave_equivalence)))
+            .collect();
+        moves.sort_by(|&(_, a), &(_, b)| b.partial_cmp(&a).unwrap_or(Equal));
+        moves.into_iter().map(|(m, _)| m).collect()
+    }
+
+    fn win_rates(&self, color: Color, game: &Game) -> Vec<(Move, f32)> {
+        self.search(color, game)
+            .iter()
+            .filter(|&(_, ms)| ms.plays > 0)
+            .map(|(m, ms)| (*m, ms.win_ratio()))
+            .collect()
+    }
+
+    fn set_interrupt(&mut self, interrupted: Arc<AtomicBool>) {
+        self.interrupted = interrupted;
+    }
+
+    fn set_live_gfx(&mut self, live_gfx: bool) {
+        self.live_gfx = live_gfx;
+    }
+
 }
@@ -0,0 +1,81 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Move;
+use patterns::Patterns;
+use super::McConfig;
+use patterns::features::distance;
+
+use std::cmp::min;
+use std::num::Float;
+
+// Estimates how good a move is before any playouts are run for it.
+// The weights of the heuristics come from the config.
+pub fn heuristic(board: &Board, m: &Move, patterns: &Patterns, config: &McConfig) -> f32 {
+    if m.is_pass() {
+        return 0.5;
+    }
+    let coord = m.coord();
+    let color = *m.color();
+    let mut value = 0.5;
+    let adjacent_in_atari = |c: Color| board.neighbours(coord)
+        .iter()
+        .filter(|n| board.color(*n) == c)
+        .any(|n| board.get_chain(*n).unwrap().liberties().len() == 1);
+    if adjacent_in_atari(color.opposite()) {
+        value += config.prior_capture;
+    }
+    if adjacent_in_atari(color) && escapes_atari(board, m) {
+        value += config.prior_atari_escape;
+    }
+    // Trained pattern weights below 1 mean that the pattern is
+    // played less often than an average move.
+    match patterns.weight(board, coord, color) {
+        Some(weight) if weight >= 1.0 => value += config.prior_pattern,
+        _ => {}
+    }
+    match board.last_move() {
+        Some(last) if !last.is_pass() && distance(last.coord(), coord) <= 3 => value += config.prior_near_last,
+        _ => {}
+    }
+    match line(board, coord) {
+        1 => value += config.prior_first_line,
+        2 => value += config.prior_second_line,
+        _ => {}
+    }
+    value.max(0.0).min(1.0)
+}
+
+// The chain the move gets added to has more than one liberty.
+fn escapes_atari(board: &Board, m: &Move) -> bool {
+    let mut after = board.clone();
+    after.play(*m).is_ok() && after.get_chain(m.coord()).unwrap().liberties().len() > 1
+}
+
+// The line of the board the coord is on, counting from the closest
+// edge, starting at 1.
+fn line(board: &Board, coord: Coord) -> u8 {
+    let size = board.size();
+    min(min(coord.col, size + 1 - coord.col), min(coord.row, size + 1 - coord.row))
+}
@@ -22,11 +22,22 @@
 #![cfg(test)]
 
 use board::Black;
+use board::Board;
+use board::Pass;
+use board::Play;
+use board::White;
 use engine::Engine;
 use game::Game;
+use patterns::Patterns;
 use ruleset::KgsChinese;
-use super::{McEngine, MoveStats};
+use ruleset::Minimal;
+use super::{McConfig, McEngine, MoveStats};
+use super::prior::heuristic;
 
+use std::collections::HashMap;
+use std::io::TempDir;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use test::Bencher;
 
 #[test]
@@ -34,3 +45,201 @@ fn newly_produced_move_stats_should_have_0pc_win_ratio() {
   let ms = MoveStats::new();
   assert_eq!(ms.win_ratio(), 0f32);
 }
+
+#[test]
+fn without_amaf_results_the_value_is_the_win_ratio() {
+  let mut ms = MoveStats::new();
+  ms.won();
+  ms.lost();
+  assert_eq!(ms.value(1000f32), 0.5f32);
+}
+
+#[test]
+fn the_amaf_results_dominate_with_few_playouts() {
+  let mut ms = MoveStats::new();
+  ms.lost();
+  for _ in range(0us, 10) {
+    ms.rave_won();
+  }
+  assert!(ms.value(1000f32) > 0.9f32);
+}
+
+#[test]
+fn the_amaf_results_fade_with_more_playouts() {
+  let mut ms = MoveStats::new();
+  for _ in range(0us, 10000) {
+    ms.lost();
+    ms.rave_won();
+  }
+  assert!(ms.value(1000f32) < 0.2f32);
+}
+
+#[test]
+fn an_equivalence_of_0_disables_rave() {
+  let mut ms = MoveStats::new();
+  ms.lost();
+  ms.rave_won();
+  assert_eq!(ms.value(0f32), 0f32);
+}
+
+#[test]
+fn the_prior_counts_as_virtual_playouts() {
+  let mut ms = MoveStats::with_prior(1f32, 10f32);
+  for _ in range(0us, 10) {
+    ms.lost();
+  }
+  assert_eq!(ms.win_ratio(), 0f32);
+  assert_eq!(ms.value(0f32), 0.5f32);
+}
+
+#[test]
+fn the_progressive_bias_fades_with_more_playouts() {
+  let mut ms = MoveStats::with_prior(0.8f32, 10f32);
+  let before = ms.progressive_bias(1f32);
+  ms.won();
+  assert!(ms.progressive_bias(1f32) < before);
+}
+
+#[test]
+fn passing_has_a_neutral_prior() {
+  let b = Board::new(9, 6.5, Minimal);
+  assert_eq!(heuristic(&b, &Pass(Black), &Patterns::mogo(), &McConfig::new()), 0.5f32);
+}
+
+#[test]
+fn first_line_moves_have_a_bad_prior() {
+  let b = Board::new(9, 6.5, Minimal);
+  assert!(heuristic(&b, &Play(Black, 1, 5), &Patterns::mogo(), &McConfig::new()) < 0.5f32);
+}
+
+#[test]
+fn the_weights_of_the_prior_come_from_the_config() {
+  let b = Board::new(9, 6.5, Minimal);
+  let mut config = McConfig::new();
+  config.prior_first_line = -0.4;
+  let m = Play(Black, 1, 5);
+  assert!(heuristic(&b, &m, &Patterns::mogo(), &config) < heuristic(&b, &m, &Patterns::mogo(), &McConfig::new()));
+}
+
+#[test]
+fn capturing_has_a_good_prior() {
+  let mut b = Board::new(9, 6.5, Minimal);
+  b.play(Play(Black, 4, 5));
+  b.play(Play(White, 5, 5));
+  b.play(Play(Black, 6, 5));
+  b.play(Play(White, 1, 1));
+  b.play(Play(Black, 5, 6));
+  b.play(Play(White, 1, 9));
+  assert!(heuristic(&b, &Play(Black, 5, 4), &Patterns::mogo(), &McConfig::new()) > 0.7f32);
+}
+
+#[test]
+fn escaping_from_atari_has_a_good_prior() {
+  let mut b = Board::new(9, 6.5, Minimal);
+  b.play(Play(Black, 5, 5));
+  b.play(Play(White, 4, 5));
+  b.play(Play(Black, 1, 1));
+  b.play(Play(White, 6, 5));
+  b.play(Play(Black, 1, 9));
+  b.play(Play(White, 5, 6));
+  assert!(heuristic(&b, &Play(Black, 5, 4), &Patterns::mogo(), &McConfig::new()) > 0.6f32);
+}
+
+#[test]
+fn the_search_starts_with_a_few_candidates() {
+  let engine = McEngine::new();
+  assert_eq!(engine.widened(0), 5);
+  assert_eq!(engine.widened(39), 5);
+  assert_eq!(engine.widened(40), 6);
+}
+
+#[test]
+fn more_candidates_get_considered_with_more_playouts() {
+  let engine = McEngine::new();
+  assert!(engine.widened(1000) > engine.widened(100));
+  assert!(engine.widened(100000) > engine.widened(1000));
+}
+
+#[test]
+fn the_config_parameters_can_be_set_by_name() {
+  let mut config = McConfig::new();
+  config.set("exploration", 0.5f32);
+  config.set("playouts", 200f32);
+  assert_eq!(config.exploration, 0.5f32);
+  assert_eq!(config.get("playouts"), 200f32);
+}
+
+#[test]
+fn the_widening_has_to_grow() {
+  let mut config = McConfig::new();
+  assert!(config.validate().is_ok());
+  config.widening_factor = 1.0;
+  assert!(config.validate().is_err());
+  config.widening_factor = 1.4;
+  config.widening_plays = 0.0;
+  assert!(config.validate().is_err());
+}
+
+#[test]
+#[should_fail]
+fn an_engine_with_an_invalid_config_cant_be_created() {
+  let mut config = McConfig::new();
+  config.widening_factor = 0.5;
+  McEngine::with_config(config);
+}
+
+#[test]
+fn the_config_can_be_saved_and_loaded() {
+  let mut config = McConfig::new();
+  config.rave_equivalence = 500f32;
+  config.widening_factor = 1.75f32;
+  let dir = TempDir::new("mc").unwrap();
+  let path = dir.path().join("config.txt");
+  config.save(path.clone());
+  let loaded = McConfig::from_path(path);
+  for name in McConfig::names().iter() {
+    assert_eq!(config.get(*name), loaded.get(*name));
+  }
+}
+
+#[test]
+fn the_win_rates_are_between_0_and_1() {
+  let mut config = McConfig::new();
+  config.playouts = 2;
+  let engine = McEngine::with_config(config).seeded([1, 2, 3, 4]);
+  let game = Game::new(5, 6.5, KgsChinese);
+  let rates = engine.win_rates(Black, &game);
+  assert!(rates.len() > 0);
+  for &(m, rate) in rates.iter() {
+    assert_eq!(Black, *m.color());
+    assert!(rate >= 0f32 && rate <= 1f32);
+  }
+}
+
+#[test]
+fn an_interrupted_search_plays_the_move_with_the_best_prior() {
+  let mut engine = McEngine::new();
+  engine.set_interrupt(Arc::new(AtomicBool::new(true)));
+  let game = Game::new(9, 6.5, KgsChinese);
+  assert_eq!(0, engine.win_rates(Black, &game).len());
+  let m = engine.gen_move(Black, &game);
+  assert!(!m.is_pass());
+  assert!(game.play(m).is_ok());
+}
+
+#[test]
+fn the_live_gfx_shows_the_best_move_and_the_win_rates() {
+  let engine = McEngine::new();
+  let mut stats = HashMap::new();
+  let mut ms = MoveStats::new();
+  ms.won();
+  ms.won();
+  ms.lost();
+  ms.lost();
+  stats.insert(Play(Black, 4, 4), ms);
+  stats.insert(Play(Black, 3, 3), MoveStats::new());
+  let mut variations = HashMap::new();
+  variations.insert(Play(Black, 4, 4), vec!(Play(White, 3, 4)));
+  let gfx = engine.gfx(Black, &stats, &variations, 4);
+  assert_eq!("gogui-gfx:\nVAR b D4 w C4\nLABEL D4 50\nTEXT 4 playouts, best D4\n\n", gfx.as_slice());
+}
@@ -19,15 +19,45 @@
  *                                                                      *
  ************************************************************************/
 
+pub use self::book::BookEngine;
+pub use self::external::ExternalGtpEngine;
+pub use self::mc::McConfig;
 pub use self::mc::McEngine;
+pub use self::policy::PolicyEngine;
 pub use self::random::RandomEngine;
 use board::Color;
 use board::Move;
 use game::Game;
 
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+mod book;
+mod external;
 mod mc;
+mod policy;
 mod random;
 
 pub trait Engine {
     fn gen_move(&self, Color, &Game) -> Move;
+
+    // The moves the engine would play, best first. Engines that
+    // don't rank their moves only return the one they'd play.
+    fn ranked_moves(&self, color: Color, game: &Game) -> Vec<Move> {
+        vec!(self.gen_move(color, game))
+    }
+
+    // The share of won playouts for the moves the engine searched.
+    // Engines that don't run playouts don't return any.
+    fn win_rates(&self, _: Color, _: &Game) -> Vec<(Move, f32)> {
+        vec!()
+    }
+
+    // A running search stops and returns the best move so far once
+    // the flag is set.
+    fn set_interrupt(&mut self, _: Arc<AtomicBool>) {}
+
+    // Print the progress of the search to stderr as GoGui live
+    // graphics.
+    fn set_live_gfx(&mut self, _: bool) {}
 }
@@ -0,0 +1,61 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Color;
+use board::Move;
+use engine::Engine;
+use game::Game;
+use patterns::Patterns;
+
+use std::cmp::Ordering::Equal;
+
+// Plays the move with the highest Bradley-Terry strength according
+// to the pattern weights, without any search.
+pub struct PolicyEngine {
+    patterns: Patterns
+}
+
+impl PolicyEngine {
+    pub fn new(patterns: Patterns) -> PolicyEngine {
+        PolicyEngine { patterns: patterns }
+    }
+}
+
+impl Engine for PolicyEngine {
+    fn gen_move(&self, color: Color, game: &Game) -> Move {
+        self.ranked_moves(color, game)[0]
+    }
+
+    fn ranked_moves(&self, color: Color, game: &Game) -> Vec<Move> {
+        let mut board = game.board();
+        // Asked for the player who moved last, as if the opponent
+        // had played elsewhere.
+        if board.next_player() != color {
+            board.tenuki();
+        }
+        let mut moves: Vec<(Move, f32)> = board.legal_moves()
+            .into_iter()
+            .map(|m| (m, self.patterns.strength(&board, &m)))
+            .collect();
+        moves.sort_by(|&(_, a), &(_, b)| b.partial_cmp(&a).unwrap_or(Equal));
+        moves.into_iter().map(|(m, _)| m).collect()
+    }
+}
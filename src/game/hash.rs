@@ -21,11 +21,18 @@
  ************************************************************************/
 
 use board::Black;
+use board::Board;
+use board::Coord;
 use board::Empty;
 use board::Move;
 use board::Play;
+use board::Symmetry;
 use board::White;
+use board::symmetry::TransformedBoard;
 
+use std::rand::Rng;
+use std::rand::SeedableRng;
+use std::rand::XorShiftRng;
 use std::rand::random;
 
 #[derive(Show)]
@@ -47,6 +54,21 @@ impl ZobristHashTable {
         ZobristHashTable {table: table, size: size}
     }
 
+    // A table that is the same every time it's created with the same
+    // seed, e.g. to store hashes in a file.
+    pub fn seeded(size: u8, seed: [u32; 4]) -> ZobristHashTable {
+        let mut rng: XorShiftRng = SeedableRng::from_seed(seed);
+        let mut table = Vec::new();
+
+        for _ in range(0i8, 3) {
+            for _ in range(0, (size as usize)*(size as usize)) {
+                table.push(rng.gen::<u64>());
+            }
+        }
+
+        ZobristHashTable {table: table, size: size}
+    }
+
     pub fn size(&self) -> u8 {
         self.size
     }
@@ -70,6 +92,38 @@ impl ZobristHashTable {
         self.add_stone_to_hash(hash, m)
     }
 
+    // The smallest of the hashes of the 8 symmetric images of the
+    // board, i.e. the same key for all positions that only differ
+    // by a rotation or reflection.
+    pub fn canonical_hash(&self, board: &Board) -> u64 {
+        let (hash, _) = self.canonical(board);
+        hash
+    }
+
+    // The canonical hash together with the symmetries that transform
+    // the board into the orientation the hash belongs to. There's
+    // more than one if the position itself is symmetric.
+    pub fn canonical(&self, board: &Board) -> (u64, Vec<Symmetry>) {
+        let hashes: Vec<(u64, Symmetry)> = Symmetry::all()
+            .iter()
+            .map(|&s| (self.hash_from_scratch(&TransformedBoard::new(board, s)), s))
+            .collect();
+        let hash = hashes.iter().map(|&(hash, _)| hash).min().unwrap();
+        let symmetries = hashes.into_iter().filter(|&(h, _)| h == hash).map(|(_, s)| s).collect();
+        (hash, symmetries)
+    }
+
+    fn hash_from_scratch(&self, board: &TransformedBoard) -> u64 {
+        let mut hash = self.init_hash();
+        for c in Coord::for_board_size(self.size).iter() {
+            match board.color(c) {
+                Empty => {},
+                color => hash = self.add_stone_to_hash(hash, &Play(color, c.col, c.row))
+            }
+        }
+        hash
+    }
+
     fn get_hash_for(&self, m: &Move) -> u64 {
         let color_as_index = match *m.color() {
             Empty => 0,
@@ -77,6 +131,7 @@ impl ZobristHashTable {
             White => 2
         };
 
-        self.table[color_as_index*self.size as usize + (m.coord().row-1) as usize * self.size as usize + m.coord().col as usize - 1]
+        let points = self.size as usize * self.size as usize;
+        self.table[color_as_index*points + m.coord().to_index(self.size)]
     }
 }
@@ -20,6 +20,7 @@
  *                                                                      *
  ************************************************************************/
 
+pub use self::hash::ZobristHashTable;
 use board::Black;
 use board::Board;
 use board::Color;
@@ -31,7 +32,6 @@ use board::Play;
 use board::White;
 use ruleset::Ruleset;
 use score::Score;
-use self::hash::ZobristHashTable;
 
 use std::fmt;
 use core::fmt::String;
@@ -44,6 +44,7 @@ mod test;
 pub struct Game<'a> {
     board: Board<'a>,
     move_number: u8,
+    moves: Vec<Move>,
     previous_boards_hashes: Vec<u64>,
     zobrist_base_table: Rc<ZobristHashTable>
 }
@@ -56,6 +57,7 @@ impl<'a> Game<'a> {
         Game {
             board: new_board,
             move_number: 0,
+            moves: Vec::new(),
             previous_boards_hashes: vec!(zobrist_base_table.init_hash()),
             zobrist_base_table: zobrist_base_table
         }
@@ -69,6 +71,7 @@ impl<'a> Game<'a> {
                 let mut new_game_state = self.clone();
                 new_game_state.board = new_board;
                 new_game_state.move_number += 1;
+                new_game_state.moves.push(m);
                 if !m.is_pass() {
                     let hash = new_game_state.compute_hash(&m);
                     if new_game_state.previous_boards_hashes.contains(&hash) {
@@ -93,6 +96,20 @@ impl<'a> Game<'a> {
         hash
     }
 
+    pub fn hash(&self) -> u64 {
+        *self.previous_boards_hashes.last().unwrap()
+    }
+
+    pub fn zobrist_table(&self) -> &ZobristHashTable {
+        &*self.zobrist_base_table
+    }
+
+    // Same for all positions that only differ by a rotation or
+    // reflection of the board.
+    pub fn canonical_hash(&self) -> u64 {
+        self.zobrist_base_table.canonical_hash(&self.board)
+    }
+
     // Note: This method uses 1-1 as the origin point, not 0-0. 19-19 is a valid coordinate in a 19-sized board, while 0-0 is not.
     //       this is done because I think it makes more sense in the context of go. (Least surprise principle, etc...)
     pub fn get(&self, col: u8, row: u8) -> Color {
@@ -107,6 +124,11 @@ impl<'a> Game<'a> {
         self.move_number
     }
 
+    // All moves played so far, in order.
+    pub fn moves(&self) -> &Vec<Move> {
+        &self.moves
+    }
+
     pub fn is_over(&self) -> bool {
         self.board.is_game_over()
     }
@@ -119,12 +141,14 @@ impl<'a> Game<'a> {
         self.board.size()
     }
 
+    // The final score, with the dead stones counting for the
+    // opponent.
     pub fn score(&self) -> Score {
-        self.board.score()
+        Score::final_score(&self.board)
     }
 
     pub fn winner(&self) -> Color {
-        self.board.winner()
+        self.score().color()
     }
 
     pub fn set_komi(&mut self, komi: f32) {
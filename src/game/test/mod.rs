@@ -28,11 +28,20 @@ use board::Pass;
 use board::Play;
 use board::White;
 use game::Game;
+use game::ZobristHashTable;
 use ruleset::KgsChinese;
 use ruleset::Minimal;
 
 mod ko;
 
+#[test]
+fn moves_returns_the_moves_played_so_far() {
+    let g = Game::new(5, 6.5, Minimal);
+    let g = g.play(Play(Black, 1, 1)).unwrap();
+    let g = g.play(Pass(White)).unwrap();
+    assert_eq!(&vec!(Play(Black, 1, 1), Pass(White)), g.moves());
+}
+
 #[test]
 fn should_start_counting_moves_at_0() {
     let g = Game::new(5, 6.5, Minimal);
@@ -73,3 +82,31 @@ fn next_player_should_return_board_next_player() {
     let g = Game::new(3, 6.5, KgsChinese);
     assert_eq!(g.board.next_player(), g.next_player());
 }
+
+#[test]
+fn symmetric_positions_have_the_same_canonical_hash() {
+    let g = Game::new(9, 6.5, KgsChinese);
+    let corner = g.play(Play(Black, 3, 1)).unwrap();
+    let transposed = g.play(Play(Black, 1, 3)).unwrap();
+    let rotated = g.play(Play(Black, 9, 3)).unwrap();
+    assert!(corner.hash() != transposed.hash());
+    assert_eq!(corner.canonical_hash(), transposed.canonical_hash());
+    assert_eq!(corner.canonical_hash(), rotated.canonical_hash());
+}
+
+#[test]
+fn different_positions_have_different_canonical_hashes() {
+    let g = Game::new(9, 6.5, KgsChinese);
+    let corner = g.play(Play(Black, 3, 1)).unwrap();
+    let center = g.play(Play(Black, 5, 5)).unwrap();
+    assert!(corner.canonical_hash() != center.canonical_hash());
+}
+
+#[test]
+fn seeded_hash_tables_are_the_same_for_the_same_seed() {
+    let a = ZobristHashTable::seeded(9, [1, 2, 3, 4]);
+    let b = ZobristHashTable::seeded(9, [1, 2, 3, 4]);
+    let c = ZobristHashTable::seeded(9, [4, 3, 2, 1]);
+    assert_eq!(a.init_hash(), b.init_hash());
+    assert!(a.init_hash() != c.init_hash());
+}
@@ -0,0 +1,131 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::White;
+use engine::Engine;
+use game::Game;
+use score::Influence;
+
+use std::num::Float;
+
+// The analysis commands for GoGui in the type/label/command format
+// of gogui-analyze_commands.
+pub fn analyze_commands() -> String {
+    vec!(
+        "gfx/Chain Liberties/chain_liberties",
+        "plist/Legal Moves/legal_moves",
+        "string/Zobrist Hash/zobrist_hash",
+        "gfx/Win Rates/win_rates",
+        "gfx/Territory/territory",
+        "dboard/Ownership/ownership"
+    ).connect("\n")
+}
+
+// Labels every stone with the number of liberties of its chain.
+pub fn chain_liberties(board: &Board) -> String {
+    let mut labels = Vec::new();
+    for chain in board.chains().iter() {
+        for c in chain.coords().iter() {
+            labels.push(format!("{} {}", c.to_gtp(), chain.liberties().len()));
+        }
+    }
+    gfx(vec!(("LABEL", labels)))
+}
+
+pub fn legal_moves(game: &Game) -> String {
+    game.legal_moves()
+        .iter()
+        .filter(|m| !m.is_pass())
+        .map(|m| m.coord().to_gtp())
+        .collect::<Vec<String>>()
+        .connect(" ")
+}
+
+pub fn zobrist_hash(game: &Game) -> String {
+    format!("{:016x}", game.hash())
+}
+
+// Colors the searched moves of the next player from red (no
+// playouts won) to green (all won) and labels them with the win rate
+// in percent.
+pub fn win_rates(engine: &Engine, game: &Game) -> String {
+    let mut colors = Vec::new();
+    let mut labels = Vec::new();
+    let mut text = Vec::new();
+    for &(m, rate) in engine.win_rates(game.next_player(), game).iter() {
+        let percent = (rate * 100.0).round() as u32;
+        if m.is_pass() {
+            text.push(format!("pass {}%", percent));
+        } else {
+            let green = (rate * 255.0).round() as u8;
+            colors.push(format!("#{:02x}{:02x}00 {}", 255 - green, green, m.coord().to_gtp()));
+            labels.push(format!("{} {}", m.coord().to_gtp(), percent));
+        }
+    }
+    let mut lines: Vec<String> = colors.iter().map(|c| format!("COLOR {}", c)).collect();
+    lines.push(gfx(vec!(("LABEL", labels), ("TEXT", text))));
+    lines.retain(|l| l.len() > 0);
+    lines.connect("\n")
+}
+
+// Marks the empty intersections that the influence function assigns
+// to either player and shows the estimated score.
+pub fn territory(game: &Game) -> String {
+    let board = game.board();
+    let influence = Influence::new(&board);
+    let owned = |color: Color| Coord::for_board_size(board.size())
+        .iter()
+        .filter(|c| board.color(*c) == Empty && influence.owner(*c) == color)
+        .map(|c| c.to_gtp())
+        .collect::<Vec<String>>();
+    gfx(vec!(("BLACK", owned(Black)), ("WHITE", owned(White)), ("TEXT", vec!(influence.estimate(game.komi())))))
+}
+
+// The ownership of each intersection between 1.0 (black) and -1.0
+// (white), one row per line starting at the top.
+pub fn ownership(game: &Game) -> String {
+    let size = game.size();
+    let ownership = Influence::new(&game.board()).ownership();
+    range(1, size + 1).rev()
+        .map(|row| {
+            range(1, size + 1)
+                .map(|col| format!("{:.2}", ownership[Coord::new(col, row).to_index(size)]))
+                .collect::<Vec<String>>()
+                .connect(" ")
+        })
+        .collect::<Vec<String>>()
+        .connect("\n")
+}
+
+// One line per gfx command, leaving out the commands without
+// arguments.
+fn gfx(commands: Vec<(&str, Vec<String>)>) -> String {
+    commands.iter()
+        .filter(|&&(_, ref args)| args.len() > 0)
+        .map(|&(name, ref args)| format!("{} {}", name, args.connect(" ")))
+        .collect::<Vec<String>>()
+        .connect("\n")
+}
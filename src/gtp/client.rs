@@ -0,0 +1,173 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Color;
+use board::Coord;
+use board::Move;
+use board::Pass;
+use board::Play;
+
+use std::io::BufferedReader;
+use std::io::pipe::PipeStream;
+use std::io::process::Command;
+use std::io::process::MustDieSignal;
+use std::io::process::Process;
+use std::io::process::StdioContainer::InheritFd;
+use std::io::timer::sleep;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread::Thread;
+use std::time::Duration;
+use time::precise_time_ns;
+
+// Talks to a GTP engine running as a child process. The engine's
+// stderr goes straight to ours.
+pub struct Client {
+    process: Process,
+    reader:  BufferedReader<PipeStream>,
+    writer:  PipeStream
+}
+
+impl Client {
+    // The command is run by the shell, so it can contain arguments.
+    // The shell replaces itself with the engine, so that killing the
+    // process kills the engine.
+    pub fn spawn(command: &str) -> Result<Client, String> {
+        Client::start(Command::new("sh").arg("-c").arg(format!("exec {}", command)), command)
+    }
+
+    // Runs the program with the arguments as they are, without
+    // going through the shell.
+    pub fn spawn_args(program: &Path, args: &[String]) -> Result<Client, String> {
+        Client::start(Command::new(program).args(args), format!("{}", program.display()).as_slice())
+    }
+
+    fn start(command: &mut Command, name: &str) -> Result<Client, String> {
+        let mut process = match command.stderr(InheritFd(2)).spawn() {
+            Ok(process) => process,
+            Err(e)      => return Err(format!("Couldn't start '{}': {}", name, e))
+        };
+        let reader = BufferedReader::new(process.stdout.take().unwrap());
+        let writer = process.stdin.take().unwrap();
+        Ok(Client { process: process, reader: reader, writer: writer })
+    }
+
+    pub fn send(&mut self, command: &str) -> Result<String, String> {
+        match self.writer.write_str(format!("{}\n", command).as_slice()).and_then(|_| self.writer.flush()) {
+            Ok(_)  => self.read_response(),
+            Err(e) => Err(format!("Couldn't send '{}': {}", command, e))
+        }
+    }
+
+    // Kills the engine if it doesn't respond within the given number
+    // of seconds, which makes this and all later sends fail.
+    pub fn send_with_timeout(&mut self, command: &str, seconds: f64) -> Result<String, String> {
+        let done = Arc::new(AtomicBool::new(false));
+        let watchdog_done = done.clone();
+        let pid = self.process.id();
+        let deadline = precise_time_ns() + (seconds.max(0.0) * 1e9) as u64;
+        Thread::spawn(move || {
+            while !watchdog_done.load(Ordering::SeqCst) {
+                if precise_time_ns() >= deadline {
+                    let _ = Process::kill(pid, MustDieSignal);
+                    return;
+                }
+                sleep(Duration::milliseconds(10));
+            }
+        });
+        let response = self.send(command);
+        done.store(true, Ordering::SeqCst);
+        response
+    }
+
+    // The name and version of the engine, e.g. "GNU Go 3.8".
+    pub fn name(&mut self) -> String {
+        let name = self.send("name").unwrap_or(String::from_str("unknown"));
+        match self.send("version") {
+            Ok(ref version) if version.len() > 0 => format!("{} {}", name, version),
+            _                                    => name
+        }
+    }
+
+    // Gives the engine a second to quit before killing it.
+    fn quit(&mut self) {
+        let _ = self.send("quit");
+        self.process.set_timeout(Some(1000));
+        if self.process.wait().is_err() {
+            let _ = self.process.signal_kill();
+            let _ = self.process.wait();
+        }
+    }
+
+    // A response ends with an empty line.
+    fn read_response(&mut self) -> Result<String, String> {
+        let mut lines = Vec::new();
+        loop {
+            let line = match self.reader.read_line() {
+                Ok(line) => line,
+                Err(e)   => return Err(format!("Couldn't read the response: {}", e))
+            };
+            let trimmed = line.as_slice().trim_right();
+            if trimmed.len() == 0 {
+                if lines.len() > 0 { break; } else { continue; }
+            }
+            lines.push(String::from_str(trimmed));
+        }
+        parse_response(lines.connect("\n").as_slice())
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        self.quit();
+    }
+}
+
+// Successful responses start with "=", failures with "?", both
+// optionally followed by the id of the command.
+pub fn parse_response(response: &str) -> Result<String, String> {
+    let re = regex!(r"(?s)^([=?])\d*\s*(.*)$");
+    match re.captures(response) {
+        Some(caps) => {
+            let text = String::from_str(caps.at(2).unwrap_or(""));
+            if caps.at(1) == Some("=") { Ok(text) } else { Err(text) }
+        },
+        None => Err(format!("Invalid response: {}", response))
+    }
+}
+
+// Only accepts passes and vertices on the board, in any case.
+pub fn parse_move(color: Color, vertex: &str, size: u8) -> Option<Move> {
+    let vertex: String = vertex.trim().chars().map(|c| c.to_lowercase()).collect();
+    if vertex.as_slice() == "pass" {
+        return Some(Pass(color));
+    }
+    if !regex!(r"^[a-hj-z][0-9]{1,2}$").is_match(vertex.as_slice()) {
+        return None;
+    }
+    let coord = Coord::from_gtp(vertex.as_slice());
+    if coord.is_inside(size) {
+        Some(Play(color, coord.col, coord.row))
+    } else {
+        None
+    }
+}
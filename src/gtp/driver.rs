@@ -22,9 +22,11 @@
 use engine::Engine;
 use super::Command;
 use super::GTPInterpreter;
-use version;
 
 use std::io::stdio::stdin;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::channel;
+use std::thread::Thread;
 
 pub struct Driver<'a> {
     engine: Box<Engine + 'a>
@@ -32,44 +34,47 @@ pub struct Driver<'a> {
 
 impl<'a> Driver<'a> {
     pub fn new(engine: Box<Engine>) {
-        let engine_name = "Iomrascálaí";
-        let engine_version = version::version();
-        let protocol_version = "2";
-
         let mut interpreter = GTPInterpreter::new(engine);
-        let mut reader = stdin();
-
-        loop {
-            // let result = interpreter.read(reader.read_line().unwrap().as_slice());
+        let interrupted = interpreter.interrupted();
+        let (sender, receiver) = channel();
 
-            // match result {
-            //     Ok(s) => print!("= {}\n\n", s),
-            //     Err(s) => print!("? {}\n\n", s),
-            //     Quit   => {
-            //         print!("= \n\n");
-            //         return;
-            //     }
-            // }
+        // Reads stdin on its own thread so that "# interrupt" lines
+        // reach the engine while it is still searching.
+        let reader_interrupted = interrupted.clone();
+        Thread::spawn(move || {
+            let mut reader = stdin();
+            loop {
+                let line = match reader.read_line() {
+                    Ok(line) => line,
+                    Err(_)   => return
+                };
+                if line.as_slice().trim() == "# interrupt" {
+                    reader_interrupted.store(true, Ordering::SeqCst);
+                    continue;
+                }
+                // An interrupt only applies to the commands that were
+                // queued before it, not to the ones after it.
+                reader_interrupted.store(false, Ordering::SeqCst);
+                if sender.send(line).is_err() {
+                    return;
+                }
+            }
+        });
 
-            let command = interpreter.read(reader.read_line().unwrap().as_slice());
+        loop {
+            let line = match receiver.recv() {
+                Ok(line) => line,
+                Err(_)   => return
+            };
+            let command = interpreter.read(line.as_slice());
 
+            match command.response() {
+                Ok(s)  => print!("= {}\n\n", s),
+                Err(s) => print!("? {}\n\n", s)
+            }
             match command {
-                Command::Name            => print!("= {}\n\n", engine_name),
-                Command::Version         => print!("= {}\n\n", engine_version),
-                Command::ProtocolVersion => print!("= {}\n\n", protocol_version),
-                Command::ListCommands(s) => print!("= {}\n\n", s),
-                Command::KnownCommand(b) => print!("= {}\n\n", b),
-                Command::BoardSize       => print!("= \n\n"),
-                Command::ClearBoard      => print!("= \n\n"),
-                Command::Komi            => print!("= \n\n"),
-                Command::Play            => print!("= \n\n"),
-                Command::PlayError(m)    => print!("? Illegal move: {:?}\n\n", m),
-                Command::GenMove(s)      => print!("= {}\n\n", s),
-                Command::GenMoveError(m) => print!("? Illegal move: {:?}\n\n", m),
-                Command::ShowBoard(s)    => print!("= {}\n\n", s),
-                Command::Quit            => {print!("= \n\n"); return;},
-                Command::FinalScore(s)   => {print!("= {}\n\n", s)},
-                _                        => {print!("? unknown command\n\n");}
+                Command::Quit => return,
+                _             => {}
             }
         }
 
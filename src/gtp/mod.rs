@@ -22,11 +22,22 @@
 
 
 use board::Color;
+use board::Coord;
 use board::movement::Move;
 use engine::Engine;
 use game::Game;
 use ruleset::KgsChinese;
+use score::FinalStatus;
+use score::Score;
+use sgf::Parser;
+use tactics::solver::Solver;
+use version;
 
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+mod analyze;
+pub mod client;
 pub mod driver;
 mod test;
 
@@ -48,23 +59,67 @@ pub enum Command {
     ShowBoard(String),
     Empty,
     Error,
-    FinalScore(String)
+    FinalScore(String),
+    FinalStatusList(String),
+    TsumegoStatus(String),
+    LoadSgf,
+    LoadSgfError(String),
+    AnalyzeCommands(String),
+    Analysis(String),
+    Interrupt,
+    LiveGfx
+}
+
+impl Command {
+    // The text of the GTP response, Err for a failure response.
+    pub fn response(&self) -> Result<String, String> {
+        match *self {
+            Command::Name                   => Ok(String::from_str("Iomrascálaí")),
+            Command::Version                => Ok(version::version().to_string()),
+            Command::ProtocolVersion        => Ok(String::from_str("2")),
+            Command::ListCommands(ref s)    => Ok(s.clone()),
+            Command::KnownCommand(b)        => Ok(format!("{}", b)),
+            Command::BoardSize              => Ok(String::new()),
+            Command::ClearBoard             => Ok(String::new()),
+            Command::Komi                   => Ok(String::new()),
+            Command::Play                   => Ok(String::new()),
+            Command::PlayError(m)           => Err(format!("Illegal move: {:?}", m)),
+            Command::GenMove(ref s)         => Ok(s.clone()),
+            Command::GenMoveError(m)        => Err(format!("Illegal move: {:?}", m)),
+            Command::ShowBoard(ref s)       => Ok(s.clone()),
+            Command::Quit                   => Ok(String::new()),
+            Command::FinalScore(ref s)      => Ok(s.clone()),
+            Command::FinalStatusList(ref s) => Ok(s.clone()),
+            Command::TsumegoStatus(ref s)   => Ok(s.clone()),
+            Command::LoadSgf                => Ok(String::new()),
+            Command::LoadSgfError(ref s)    => Err(s.clone()),
+            Command::AnalyzeCommands(ref s) => Ok(s.clone()),
+            Command::Analysis(ref s)        => Ok(s.clone()),
+            Command::Interrupt              => Ok(String::new()),
+            Command::LiveGfx                => Ok(String::new()),
+            _                               => Err(String::from_str("unknown command"))
+        }
+    }
 }
 
 pub struct GTPInterpreter<'a> {
     known_commands: Vec<String>,
     game: Game<'a>,
-    engine: Box<Engine + 'a>
+    engine: Box<Engine + 'a>,
+    interrupted: Arc<AtomicBool>
 }
 
 impl<'a> GTPInterpreter<'a> {
-    pub fn new<'b>(engine: Box<Engine + 'b>) -> GTPInterpreter<'b> {
+    pub fn new<'b>(mut engine: Box<Engine + 'b>) -> GTPInterpreter<'b> {
         let komi = 6.5;
         let boardsize = 19;
+        let interrupted = Arc::new(AtomicBool::new(false));
+        engine.set_interrupt(interrupted.clone());
         let mut interpreter = GTPInterpreter {
             known_commands: vec!(),
             game: Game::new(boardsize, komi, KgsChinese),
-            engine: engine
+            engine: engine,
+            interrupted: interrupted
         };
         interpreter.initialize();
         interpreter
@@ -89,6 +144,19 @@ impl<'a> GTPInterpreter<'a> {
         known_commands.push(String::from_str("komi"));
         known_commands.push(String::from_str("showboard"));
         known_commands.push(String::from_str("final_score"));
+        known_commands.push(String::from_str("final_status_list"));
+        known_commands.push(String::from_str("tsumego_status"));
+        known_commands.push(String::from_str("loadsgf"));
+        known_commands.push(String::from_str("reg_genmove"));
+        known_commands.push(String::from_str("gogui-analyze_commands"));
+        known_commands.push(String::from_str("chain_liberties"));
+        known_commands.push(String::from_str("legal_moves"));
+        known_commands.push(String::from_str("zobrist_hash"));
+        known_commands.push(String::from_str("win_rates"));
+        known_commands.push(String::from_str("territory"));
+        known_commands.push(String::from_str("ownership"));
+        known_commands.push(String::from_str("gogui-interrupt"));
+        known_commands.push(String::from_str("gogui-live_gfx"));
         known_commands
     }
 
@@ -96,6 +164,12 @@ impl<'a> GTPInterpreter<'a> {
         &self.game
     }
 
+    // Setting the flag stops the running search. GoGui does that
+    // by sending a "# interrupt" line while it waits for a response.
+    pub fn interrupted(&self) -> Arc<AtomicBool> {
+        self.interrupted.clone()
+    }
+
     pub fn komi(&self) -> f32 {
         self.game.komi()
     }
@@ -148,6 +222,10 @@ impl<'a> GTPInterpreter<'a> {
                     }
                 }
             },
+            "reg_genmove"      => {
+                let color = Color::from_gtp(command[1]);
+                Command::GenMove(self.engine.gen_move(color, &self.game).to_gtp())
+            },
             "play"             => {
                 let m = Move::from_gtp(command[1], command[2]);
                 match self.game.clone().play(m) {
@@ -163,6 +241,63 @@ impl<'a> GTPInterpreter<'a> {
             "showboard"   => Command::ShowBoard(format!("\n{}", self.game)),
             "quit"        => return Command::Quit,
             "final_score" => return Command::FinalScore(format!("{}", self.game.score())),
+            "final_status_list" => {
+                let status = match command[1] {
+                    "alive" => FinalStatus::Alive,
+                    "dead"  => FinalStatus::Dead,
+                    "seki"  => FinalStatus::Seki,
+                    _       => return Command::Error
+                };
+                let chains: Vec<String> = Score::final_status_list(&self.game.board(), status)
+                    .iter()
+                    .map(|coords| coords.iter().map(|c| c.to_gtp()).collect::<Vec<String>>().connect(" "))
+                    .collect();
+                Command::FinalStatusList(chains.connect("\n"))
+            },
+            "tsumego_status" => {
+                let board = self.game.board();
+                let target = Coord::from_gtp(command[1]);
+                if board.get_chain(target).is_none() {
+                    return Command::Error;
+                }
+                let region = Solver::enclosed_region(&board, target);
+                let solution = Solver::new(&board, region, target).solve();
+                let status: String = format!("{:?}", solution.status()).chars().map(|c| c.to_lowercase()).collect();
+                Command::TsumegoStatus(format!("{} {}", status, solution.best_move().to_gtp()))
+            },
+            "loadsgf"     => {
+                let parser = match command.get(1).map(|path| Parser::open(&Path::new(*path))) {
+                    Some(Ok(parser)) => parser,
+                    _                => return Command::LoadSgfError(String::from_str("cannot load file"))
+                };
+                let game = match command.get(2).and_then(|s| s.parse()) {
+                    Some(move_number) => parser.game_before(move_number),
+                    None              => parser.game()
+                };
+                match game {
+                    Ok(g)  => {
+                        self.game = g;
+                        Command::LoadSgf
+                    },
+                    Err(_) => Command::LoadSgfError(String::from_str("illegal move in file"))
+                }
+            },
+            "gogui-analyze_commands" => Command::AnalyzeCommands(analyze::analyze_commands()),
+            "chain_liberties" => Command::Analysis(analyze::chain_liberties(&self.game.board())),
+            "legal_moves"     => Command::Analysis(analyze::legal_moves(&self.game)),
+            "zobrist_hash"    => Command::Analysis(analyze::zobrist_hash(&self.game)),
+            "win_rates"       => Command::Analysis(analyze::win_rates(&*self.engine, &self.game)),
+            "territory"       => Command::Analysis(analyze::territory(&self.game)),
+            "ownership"       => Command::Analysis(analyze::ownership(&self.game)),
+            "gogui-interrupt" => Command::Interrupt,
+            "gogui-live_gfx"  => {
+                match command.get(1) {
+                    Some(&"on")  => self.engine.set_live_gfx(true),
+                    Some(&"off") => self.engine.set_live_gfx(false),
+                    _            => return Command::Error
+                }
+                Command::LiveGfx
+            },
             _             => return Command::Error
         }
     }
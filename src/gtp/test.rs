@@ -21,15 +21,23 @@
 
 #![cfg(test)]
 
+use board::Black;
+use board::Empty;
+use board::Pass;
+use board::Play;
+use board::White;
 use engine::RandomEngine;
 use super::Command;
 use super::GTPInterpreter;
+use super::client::Client;
+use super::client::parse_move;
+use super::client::parse_response;
 
 #[test]
 fn no_newline_at_end_of_list_commands() {
     let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
     let commands    = interpreter.read("list_commands\n");
-    let expected    = "play\ngenmove\nprotocol_version\nname\nversion\nknown_command\nlist_commands\nquit\nboardsize\nclear_board\nkomi\nshowboard\nfinal_score";
+    let expected    = "play\ngenmove\nprotocol_version\nname\nversion\nknown_command\nlist_commands\nquit\nboardsize\nclear_board\nkomi\nshowboard\nfinal_score\nfinal_status_list\ntsumego_status\nloadsgf\nreg_genmove\ngogui-analyze_commands\nchain_liberties\nlegal_moves\nzobrist_hash\nwin_rates\nterritory\nownership\ngogui-interrupt\ngogui-live_gfx";
     match commands {
         Command::ListCommands(cs) => assert_eq!(expected, cs.as_slice()),
         _                         => panic!("wrong match")
@@ -93,3 +101,199 @@ fn final_score_one_move() {
         _                          => panic!("FinalScore expected!")
     }
 }
+
+#[test]
+fn tsumego_status_solves_a_chain_in_atari() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("boardsize 5\n");
+    interpreter.read("play b a2\n");
+    interpreter.read("play w a3\n");
+    interpreter.read("play b b2\n");
+    interpreter.read("play w b3\n");
+    interpreter.read("play b b1\n");
+    interpreter.read("play w c2\n");
+    interpreter.read("play b e5\n");
+    interpreter.read("play w c1\n");
+    interpreter.read("play b e4\n");
+    match interpreter.read("tsumego_status b2\n") {
+        Command::TsumegoStatus(s) => assert_eq!("dead A1", s.as_slice()),
+        _                         => panic!("TsumegoStatus expected!")
+    }
+}
+
+#[test]
+fn tsumego_status_gives_up_on_an_open_board() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("play b k10\n");
+    match interpreter.read("tsumego_status k10\n") {
+        Command::TsumegoStatus(s) => assert_eq!("unknown pass", s.as_slice()),
+        _                         => panic!("TsumegoStatus expected!")
+    }
+}
+
+#[test]
+fn final_status_list_lists_the_chains_line_by_line() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("boardsize 5\n");
+    interpreter.read("play b a1\n");
+    interpreter.read("play w e5\n");
+    interpreter.read("play b a2\n");
+    match interpreter.read("final_status_list alive\n") {
+        Command::FinalStatusList(s) => assert_eq!("A1 A2\nE5", s.as_slice()),
+        _                           => panic!("FinalStatusList expected!")
+    }
+}
+
+#[test]
+fn loadsgf_replays_the_game() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("loadsgf fixtures/sgf/twomoves.sgf\n");
+    assert_eq!(19, interpreter.game.size());
+    assert_eq!(5.5, interpreter.game.komi());
+    assert_eq!(Black, interpreter.game.get(4, 15));
+    assert_eq!(White, interpreter.game.get(16, 7));
+}
+
+#[test]
+fn loadsgf_stops_before_the_move_number() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("loadsgf fixtures/sgf/twomoves.sgf 2\n");
+    assert_eq!(Black, interpreter.game.get(4, 15));
+    assert_eq!(Empty, interpreter.game.get(16, 7));
+}
+
+#[test]
+fn loadsgf_fails_for_a_missing_file() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    let response = interpreter.read("loadsgf fixtures/sgf/missing.sgf\n").response();
+    assert_eq!(Err(String::from_str("cannot load file")), response);
+}
+
+#[test]
+fn loadsgf_fails_for_a_malformed_file() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    let response = interpreter.read("loadsgf fixtures/sgf-invalid/malformed.sgf\n").response();
+    assert_eq!(Err(String::from_str("cannot load file")), response);
+    assert_eq!(19, interpreter.game.size());
+}
+
+#[test]
+fn the_analyze_commands_are_known_commands() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    let commands = interpreter.read("gogui-analyze_commands\n").response().unwrap();
+    for line in commands.as_slice().lines() {
+        let command = line.split('/').last().unwrap();
+        let known = interpreter.read(format!("known_command {}\n", command).as_slice()).response();
+        assert_eq!(Ok(String::from_str("true")), known);
+    }
+}
+
+#[test]
+fn chain_liberties_labels_the_stones() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("boardsize 5\n");
+    interpreter.read("play b a1\n");
+    interpreter.read("play w e5\n");
+    interpreter.read("play b a2\n");
+    let labels = interpreter.read("chain_liberties\n").response().unwrap();
+    assert_eq!("LABEL A1 3 A2 3 E5 2", labels.as_slice());
+}
+
+#[test]
+fn legal_moves_lists_the_empty_intersections() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("boardsize 3\n");
+    interpreter.read("play b b2\n");
+    let moves = interpreter.read("legal_moves\n").response().unwrap();
+    assert_eq!(8, moves.as_slice().split(' ').count());
+    assert!(!moves.as_slice().contains("B2"));
+}
+
+#[test]
+fn zobrist_hash_changes_with_the_position() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    let empty = interpreter.read("zobrist_hash\n").response().unwrap();
+    interpreter.read("play b a1\n");
+    let hash = interpreter.read("zobrist_hash\n").response().unwrap();
+    assert_eq!(16, hash.len());
+    assert!(empty != hash);
+}
+
+#[test]
+fn win_rates_is_empty_for_engines_without_playouts() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    assert_eq!(Ok(String::new()), interpreter.read("win_rates\n").response());
+}
+
+#[test]
+fn territory_shows_the_estimated_score() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("boardsize 5\n");
+    assert_eq!(Ok(String::from_str("TEXT W+6.5")), interpreter.read("territory\n").response());
+}
+
+#[test]
+fn ownership_has_a_row_per_line() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    interpreter.read("boardsize 5\n");
+    let ownership = interpreter.read("ownership\n").response().unwrap();
+    let rows: Vec<&str> = ownership.as_slice().lines().collect();
+    assert_eq!(5, rows.len());
+    assert!(rows.iter().all(|row| *row == "0.00 0.00 0.00 0.00 0.00"));
+}
+
+#[test]
+fn gogui_live_gfx_can_be_switched_on_and_off() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    assert_eq!(Ok(String::new()), interpreter.read("gogui-live_gfx on\n").response());
+    assert_eq!(Ok(String::new()), interpreter.read("gogui-live_gfx off\n").response());
+    assert!(interpreter.read("gogui-live_gfx maybe\n").response().is_err());
+}
+
+#[test]
+fn gogui_interrupt_is_supported() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    assert_eq!(Ok(String::new()), interpreter.read("gogui-interrupt\n").response());
+}
+
+#[test]
+fn parse_response_strips_the_status_and_the_id() {
+    assert_eq!(Ok(String::from_str("C3")), parse_response("= C3"));
+    assert_eq!(Ok(String::from_str("C3")), parse_response("=12 C3"));
+    assert_eq!(Ok(String::from_str("")), parse_response("="));
+}
+
+#[test]
+fn parse_response_returns_failures_as_errors() {
+    assert_eq!(Err(String::from_str("illegal move")), parse_response("? illegal move"));
+    assert!(parse_response("C3").is_err());
+}
+
+#[test]
+fn parse_response_keeps_multi_line_responses() {
+    assert_eq!(Ok(String::from_str("A1 B2\nC3")), parse_response("= A1 B2\nC3"));
+}
+
+#[test]
+fn the_client_talks_to_an_external_engine() {
+    let mut client = Client::spawn("sh fixtures/gtp/standin.sh d4").unwrap();
+    assert_eq!("Stand-in 1.0", client.name().as_slice());
+    assert_eq!(Ok(String::from_str("d4")), client.send("genmove b"));
+    assert!(client.send("unknown_command").is_err());
+}
+
+#[test]
+fn parse_move_accepts_passes_and_vertices_on_the_board() {
+    assert_eq!(Some(Pass(Black)), parse_move(Black, "pass", 9));
+    assert_eq!(Some(Play(White, 3, 4)), parse_move(White, "c4", 9));
+    assert_eq!(Some(Play(White, 9, 9)), parse_move(White, "j9", 9));
+    assert_eq!(Some(Play(White, 3, 4)), parse_move(White, "C4", 9));
+}
+
+#[test]
+fn parse_move_rejects_everything_else() {
+    assert_eq!(None, parse_move(Black, "k10", 9));
+    assert_eq!(None, parse_move(Black, "i5", 9));
+    assert_eq!(None, parse_move(Black, "nonsense", 9));
+    assert_eq!(None, parse_move(Black, "", 9));
+}
@@ -27,24 +27,49 @@ extern crate regex;
 extern crate regex_macros;
 extern crate "rustc-serialize" as rustc_serialize;
 extern crate test;
+extern crate time;
 
+use book::Book;
+use engine::BookEngine;
 use engine::Engine;
+use engine::ExternalGtpEngine;
+use engine::McConfig;
 use engine::McEngine;
+use engine::PolicyEngine;
 use engine::RandomEngine;
 use getopts::getopts;
 use getopts::optopt;
+use gtp::GTPInterpreter;
+use gtp::client::Client;
+use patterns::Patterns;
+use selfplay::SelfPlay;
+use selfplay::sprt::Sprt;
+use twogtp::TwoGtp;
+
 use std::ascii::OwnedAsciiExt;
 use std::os::args;
+use std::os::self_exe_name;
+use std::os::set_exit_status;
 
+mod bench;
 mod board;
+mod book;
 mod cli;
 mod engine;
 mod game;
 mod gtp;
+mod patterns;
+mod perft;
 mod playout;
+mod prediction;
+mod regress;
 mod ruleset;
 mod score;
+mod selfplay;
 mod sgf;
+mod tactics;
+mod tuning;
+mod twogtp;
 mod version;
 
 fn main() {
@@ -52,6 +77,21 @@ fn main() {
     let opts = [
         optopt("m", "mode", "set control mode", "MODE"),
         optopt("e", "engine", "select an engine", "ENGINE"),
+        optopt("c", "config", "load the parameters of the mc engine from a file", "FILE"),
+        optopt("r", "rave", "set the RAVE equivalence parameter of the mc engine", "NUM"),
+        optopt("p", "patterns", "load the pattern weights from a file", "FILE"),
+        optopt("b", "book", "play from the opening book in the file first", "FILE"),
+        optopt("o", "opponent", "select the opponent engine for self-play", "ENGINE"),
+        optopt("", "opponent-config", "load the parameters of the opponent's mc engine from a file (default parameters otherwise)", "FILE"),
+        optopt("g", "games", "number of self-play games per board size", "NUM"),
+        optopt("s", "sizes", "comma separated board sizes for self-play", "SIZES"),
+        optopt("d", "sgf-dir", "directory to write the self-play games to", "DIR"),
+        optopt("x", "external", "command line of the external GTP engine for matches or the external engine", "COMMAND"),
+        optopt("", "elo0", "the Elo difference of the null hypothesis of the SPRT (default 0)", "ELO"),
+        optopt("", "elo1", "the Elo difference of the alternative hypothesis of the SPRT (default 35)", "ELO"),
+        optopt("", "alpha", "the probability of a false positive of the SPRT (default 0.05)", "NUM"),
+        optopt("", "beta", "the probability of a false negative of the SPRT (default 0.05)", "NUM"),
+        optopt("t", "time", "sudden death time per player and game in seconds for matches", "SECONDS"),
             ];
 
     let matches = match getopts(args().tail(), &opts) {
@@ -59,15 +99,153 @@ fn main() {
         Err(f) => panic!(f.to_string())
     };
 
+    let mut config = match matches.opt_str("c") {
+        Some(file) => McConfig::from_path(Path::new(file)),
+        None       => McConfig::new()
+    };
+    match matches.opt_str("r").and_then(|s| s.parse::<f32>()) {
+        Some(rave) => config.rave_equivalence = rave,
+        None       => {}
+    }
+
+    let opponent_config = match matches.opt_str("opponent-config") {
+        Some(file) => McConfig::from_path(Path::new(file)),
+        None       => McConfig::new()
+    };
+
     let engine_arg = matches.opt_str("e").map(|s| s.into_ascii_lowercase());
-    let engine = match engine_arg {
-        Some(ref s) if s.as_slice() == "mc" => Box::new(McEngine::new()) as Box<Engine>,
-        _                                   => Box::new(RandomEngine::new()) as Box<Engine>
+    let engine = make_engine(engine_arg, config, matches.opt_str("p"), matches.opt_str("x"));
+    let engine = match matches.opt_str("b") {
+        Some(file) => Box::new(BookEngine::new(Book::from_path(Path::new(file)), engine)) as Box<Engine>,
+        None       => engine
     };
 
     let mode_arg = matches.opt_str("m").map(|s| s.into_ascii_lowercase());
     match mode_arg {
         Some(ref s) if s.as_slice() == "gtp" => gtp::driver::Driver::new(engine),
+        // Usage: -m train <directory with SGF files> <output file>
+        Some(ref s) if s.as_slice() == "train" => {
+            if matches.free.len() != 2 {
+                panic!("The train mode needs an SGF directory and an output file");
+            }
+            patterns::training::train(Path::new(matches.free[0].clone()), Path::new(matches.free[1].clone()), 20)
+        },
+        // Usage: -m book <directory with SGF files> <output file> [board size]
+        Some(ref s) if s.as_slice() == "book" => {
+            if matches.free.len() < 2 {
+                panic!("The book mode needs an SGF directory and an output file");
+            }
+            let size = matches.free.get(2).and_then(|s| s.parse()).unwrap_or(19);
+            book::build(Path::new(matches.free[0].clone()), Path::new(matches.free[1].clone()), size, 30)
+        },
+        // Usage: -m selfplay -e <engine> -o <engine> [-c <file>] [--opponent-config <file>] [-g <games>] [-s <sizes>] [-d <directory>]
+        Some(ref s) if s.as_slice() == "selfplay" => {
+            let opponent_arg = matches.opt_str("o").map(|s| s.into_ascii_lowercase());
+            let engine_name = engine_arg.clone().unwrap_or(String::from_str("random"));
+            let opponent_name = opponent_arg.clone().unwrap_or(String::from_str("random"));
+            let opponent = make_engine(opponent_arg, opponent_config, matches.opt_str("p"), matches.opt_str("x"));
+            let games = matches.opt_str("g").and_then(|s| s.parse()).unwrap_or(10);
+            let sizes = matches.opt_str("s")
+                .map(|s| s.as_slice().split(',').filter_map(|size| size.parse()).collect())
+                .unwrap_or(vec!(9));
+            let selfplay = SelfPlay::new(engine, engine_name.as_slice(), opponent, opponent_name.as_slice());
+            selfplay.run(games, &sizes, 6.5, matches.opt_str("d").map(|dir| Path::new(dir)));
+        },
+        // Usage: -m sprt -e <engine> -o <engine> [-c <file>] [--opponent-config <file>] [--elo0 <elo>] [--elo1 <elo>] [--alpha <num>] [--beta <num>] [-g <max games>] [-s <size>]
+        Some(ref s) if s.as_slice() == "sprt" => {
+            let opponent_arg = matches.opt_str("o").map(|s| s.into_ascii_lowercase());
+            let engine_name = engine_arg.clone().unwrap_or(String::from_str("random"));
+            let opponent_name = opponent_arg.clone().unwrap_or(String::from_str("random"));
+            let opponent = make_engine(opponent_arg, opponent_config, matches.opt_str("p"), matches.opt_str("x"));
+            let float = |name: &str, default: f64| matches.opt_str(name).and_then(|s| s.parse()).unwrap_or(default);
+            let sprt = Sprt::new(float("elo0", 0.0), float("elo1", 35.0), float("alpha", 0.05), float("beta", 0.05));
+            let max_games = matches.opt_str("g").and_then(|s| s.parse()).unwrap_or(10000);
+            let size = matches.opt_str("s").and_then(|s| s.parse()).unwrap_or(9);
+            let selfplay = SelfPlay::new(engine, engine_name.as_slice(), opponent, opponent_name.as_slice());
+            selfplay.sprt(&sprt, max_games, size, 6.5);
+        },
+        // Usage: -m match -x <GTP command> [-e <engine>] [-g <games>] [-s <sizes>] [-d <directory>] [-t <seconds>]
+        Some(ref s) if s.as_slice() == "match" => {
+            let external = match matches.opt_str("x") {
+                Some(command) => command,
+                None          => panic!("The match mode needs the command of an external GTP engine")
+            };
+            let mut own = vec!(String::from_str("-m"), String::from_str("gtp"));
+            for opt in ["e", "c", "r", "p", "b"].iter() {
+                match matches.opt_str(*opt) {
+                    Some(value) => {
+                        own.push(format!("-{}", opt));
+                        own.push(value);
+                    },
+                    None => {}
+                }
+            }
+            let games = matches.opt_str("g").and_then(|s| s.parse()).unwrap_or(10);
+            let sizes = matches.opt_str("s")
+                .map(|s| s.as_slice().split(',').filter_map(|size| size.parse()).collect())
+                .unwrap_or(vec!(9));
+            let time = matches.opt_str("t").and_then(|s| s.parse());
+            let mut twogtp = TwoGtp::new(Client::spawn_args(&self_exe_name().unwrap(), own.as_slice()).unwrap(), Client::spawn(external.as_slice()).unwrap(), time);
+            twogtp.run(games, &sizes, 6.5, matches.opt_str("d").map(|dir| Path::new(dir)));
+        },
+        // Usage: -m tune <output file> [iterations] [-g <games per iteration>] [-s <size>]
+        Some(ref s) if s.as_slice() == "tune" => {
+            if matches.free.len() < 1 {
+                panic!("The tune mode needs an output file");
+            }
+            let iterations = matches.free.get(1).and_then(|s| s.parse()).unwrap_or(1000);
+            let games = matches.opt_str("g").and_then(|s| s.parse()).unwrap_or(2);
+            let size = matches.opt_str("s").and_then(|s| s.parse()).unwrap_or(9);
+            tuning::tune(config, iterations, games, size, Path::new(matches.free[0].clone()))
+        },
+        // Usage: -m bench [playouts per candidate move of the mc engine]
+        Some(ref s) if s.as_slice() == "bench" => {
+            let mut bench_config = config;
+            bench_config.playouts = matches.free.get(0).and_then(|s| s.parse()).unwrap_or(10);
+            bench::run(bench_config, 1000, Path::new("fixtures/sgf"))
+        },
+        // Usage: -m perft [maximum depth]
+        Some(ref s) if s.as_slice() == "perft" => {
+            let depth = matches.free.get(0).and_then(|s| s.parse()).unwrap_or(5);
+            let failures = perft::check(Path::new("fixtures/perft/reference.txt"), depth);
+            if failures.len() > 0 {
+                panic!("{} perft results don't match the reference numbers", failures.len());
+            }
+        },
+        // Usage: -m regress <test files>
+        Some(ref s) if s.as_slice() == "regress" => {
+            if matches.free.len() == 0 {
+                panic!("The regress mode needs at least one test file");
+            }
+            let files: Vec<Path> = matches.free.iter().map(|f| Path::new(f.clone())).collect();
+            let mut interpreter = GTPInterpreter::new(engine);
+            if !regress::run(&mut interpreter, &files) {
+                set_exit_status(1);
+            }
+        },
+        // Usage: -m predict <directory with SGF files>
+        Some(ref s) if s.as_slice() == "predict" => {
+            if matches.free.len() != 1 {
+                panic!("The predict mode needs an SGF directory");
+            }
+            prediction::run(engine, Path::new(matches.free[0].clone()))
+        },
         _                                    => cli::Driver::new()
     };
 }
+
+fn make_engine<'a>(name: Option<String>, config: McConfig, patterns_file: Option<String>, external: Option<String>) -> Box<Engine + 'a> {
+    let patterns = || match patterns_file {
+        Some(ref file) => Patterns::from_path(Path::new(file.clone())),
+        None           => Patterns::mogo()
+    };
+    match name {
+        Some(ref s) if s.as_slice() == "mc"     => Box::new(McEngine::with_patterns(config, patterns())) as Box<Engine>,
+        Some(ref s) if s.as_slice() == "policy" => Box::new(PolicyEngine::new(patterns())) as Box<Engine>,
+        Some(ref s) if s.as_slice() == "external" => {
+            let command = external.expect("The external engine needs the command of a GTP engine");
+            Box::new(ExternalGtpEngine::spawn(command.as_slice()).unwrap()) as Box<Engine>
+        },
+        _                                       => Box::new(RandomEngine::new()) as Box<Engine>
+    }
+}
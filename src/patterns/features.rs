@@ -0,0 +1,187 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Move;
+use board::Symmetry;
+
+use std::cmp::max;
+use std::cmp::min;
+
+// The intersections around the move that make up the larger spatial
+// pattern: Everything within a manhattan distance of 2.
+static DIAMOND: [(i16, i16); 12] = [
+    (0, -2),
+    (-1, -1), (0, -1), (1, -1),
+    (-2, 0), (-1, 0), (1, 0), (2, 0),
+    (-1, 1), (0, 1), (1, 1),
+    (0, 2)
+];
+
+// The features of a move used for move prediction. The spatial
+// patterns are identified by their canonical key, i.e. the smallest
+// key of all their rotations and reflections.
+#[derive(Clone, Copy, Show, Eq, PartialEq, Hash)]
+pub enum Feature {
+    Pattern3x3(u16),
+    Diamond(u32),
+    Capture,
+    AtariEscape,
+    Atari,
+    SelfAtari,
+    Line(u8),
+    Distance(u8),
+    PassMove
+}
+
+impl Feature {
+    pub fn name(&self) -> String {
+        match *self {
+            Feature::Pattern3x3(key) => format!("p3:{}", key),
+            Feature::Diamond(key)    => format!("p5:{}", key),
+            Feature::Capture         => String::from_str("capture"),
+            Feature::AtariEscape     => String::from_str("atari_escape"),
+            Feature::Atari           => String::from_str("atari"),
+            Feature::SelfAtari       => String::from_str("self_atari"),
+            Feature::Line(l)         => format!("line:{}", l),
+            Feature::Distance(d)     => format!("distance:{}", d),
+            Feature::PassMove        => String::from_str("pass"),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Feature> {
+        let mut parts = name.splitn(1, ':');
+        let kind = parts.next().unwrap_or("");
+        match (kind, parts.next()) {
+            ("p3", Some(key))         => key.parse().map(|k| Feature::Pattern3x3(k)),
+            ("p5", Some(key))         => key.parse().map(|k| Feature::Diamond(k)),
+            ("capture", None)         => Some(Feature::Capture),
+            ("atari_escape", None)    => Some(Feature::AtariEscape),
+            ("atari", None)           => Some(Feature::Atari),
+            ("self_atari", None)      => Some(Feature::SelfAtari),
+            ("line", Some(line))      => line.parse().map(|l| Feature::Line(l)),
+            ("distance", Some(dist))  => dist.parse().map(|d| Feature::Distance(d)),
+            ("pass", None)            => Some(Feature::PassMove),
+            _                         => None
+        }
+    }
+}
+
+// All the features of a legal move on the board.
+pub fn features(board: &Board, m: &Move) -> Vec<Feature> {
+    if m.is_pass() {
+        return vec!(Feature::PassMove);
+    }
+    let coord = m.coord();
+    let color = *m.color();
+    let mut features = vec!(
+        Feature::Pattern3x3(canonical_3x3(super::key(board, coord, color))),
+        Feature::Diamond(diamond_key(board, coord, color)));
+    let mut after = board.clone();
+    if after.play(*m).is_ok() {
+        if after.adv_stones_removed().len() > 0 {
+            features.push(Feature::Capture);
+        }
+        let was_in_atari = board.neighbours(coord)
+            .iter()
+            .filter(|c| board.color(*c) == color)
+            .any(|c| board.get_chain(*c).unwrap().liberties().len() == 1);
+        let libs = after.get_chain(coord).unwrap().liberties().len();
+        if was_in_atari && libs > 1 {
+            features.push(Feature::AtariEscape);
+        }
+        if libs == 1 {
+            features.push(Feature::SelfAtari);
+        }
+        let ataris = after.neighbours(coord)
+            .iter()
+            .filter(|c| after.color(*c) == color.opposite())
+            .any(|c| after.get_chain(*c).unwrap().liberties().len() == 1);
+        if ataris {
+            features.push(Feature::Atari);
+        }
+    }
+    let size = board.size();
+    let line = min(min(coord.col, size + 1 - coord.col), min(coord.row, size + 1 - coord.row));
+    features.push(Feature::Line(min(line, 5)));
+    match board.last_move() {
+        Some(last) if !last.is_pass() => {
+            let d = distance(last.coord(), coord);
+            if d <= 4 {
+                features.push(Feature::Distance(d));
+            }
+        },
+        _ => {}
+    }
+    features
+}
+
+// The smallest key of all the rotations and reflections of the 3x3
+// pattern.
+pub fn canonical_3x3(key: u16) -> u16 {
+    let values = super::decode(key);
+    Symmetry::all()
+        .iter()
+        .map(|&s| super::encode(super::transform(values.as_slice(), s).as_slice()))
+        .min()
+        .unwrap()
+}
+
+// All the keys of the rotations and reflections of the 3x3 pattern.
+pub fn variants_3x3(key: u16) -> Vec<u16> {
+    let values = super::decode(key);
+    Symmetry::all()
+        .iter()
+        .map(|&s| super::encode(super::transform(values.as_slice(), s).as_slice()))
+        .collect()
+}
+
+// The canonical key of the diamond shaped pattern around the
+// move. Like with the 3x3 patterns each intersection takes two bits.
+pub fn diamond_key(board: &Board, coord: Coord, color: Color) -> u32 {
+    let values: Vec<u32> = DIAMOND.iter()
+        .map(|&(dx, dy)| super::value_at(board, coord, color, dx, dy) as u32)
+        .collect();
+    Symmetry::all()
+        .iter()
+        .map(|&s| {
+            let mut transformed = values.clone();
+            for (i, &(dx, dy)) in DIAMOND.iter().enumerate() {
+                let (x, y) = s.apply((dx + 2) as u8, (dy + 2) as u8, 5);
+                let target = (x as i16 - 2, y as i16 - 2);
+                let j = DIAMOND.iter().position(|&o| o == target).unwrap();
+                transformed[j] = values[i];
+            }
+            transformed.iter().fold(0, |key, &v| (key << 2) | v)
+        })
+        .min()
+        .unwrap()
+}
+
+// The "gridcular" distance used by Pachi and Fuego: Like the
+// manhattan distance, but diagonal steps are shorter.
+pub fn distance(a: Coord, b: Coord) -> u8 {
+    let dx = max(a.col, b.col) - min(a.col, b.col);
+    let dy = max(a.row, b.row) - min(a.row, b.row);
+    dx + dy + max(dx, dy)
+}
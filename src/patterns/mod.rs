@@ -0,0 +1,234 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Move;
+use board::Symmetry;
+use self::features::Feature;
+
+use std::collections::HashMap;
+use std::io::fs::File;
+
+pub mod features;
+pub mod training;
+mod test;
+
+// The values of the intersections around the center of a pattern,
+// relative to the player to move.
+static EMPTY:     u16 = 0;
+static OWN:       u16 = 1;
+static OPPONENT:  u16 = 2;
+static OFF_BOARD: u16 = 3;
+
+// Hane, cut and edge shapes along the lines of the ones MoGo uses
+// in its playouts. The center is the move, X and O are stones of
+// either color, x and o mean anything but X and O respectively, #
+// is off the board and ? matches anything.
+static MOGO_PATTERNS: [&'static str; 12] = [
+    "XOX...???", // hane enclosing
+    "XO....?.?", // hane not cutting
+    "XO?X..x.?", // magari
+    "XOO...?.?", // thin hane
+    "XO?O.o?o?", // unprotected cut
+    "XO?O.X???", // peeped cut
+    "?X?O.Oooo", // cut in the middle
+    "OX?o.O???", // cut through a keima
+    "OX?X.O###", // block a cut on the side
+    "?X?x.O###", // block a connection on the side
+    "?XOx.x###", // sagari
+    "?OX?.O###", // cut on the side
+];
+
+// 3x3 patterns around an empty intersection. Each pattern is stored
+// in all its rotations and reflections, and with the colors swapped,
+// so a lookup is a single hash map access. Trained weights also come
+// with the gammas of all the other move features.
+pub struct Patterns {
+    gammas:  HashMap<Feature, f32>,
+    weights: HashMap<u16, f32>
+}
+
+impl Patterns {
+    pub fn new() -> Patterns {
+        Patterns { gammas: HashMap::new(), weights: HashMap::new() }
+    }
+
+    pub fn mogo() -> Patterns {
+        let mut patterns = Patterns::new();
+        for diagram in MOGO_PATTERNS.iter() {
+            patterns.add(*diagram, 1.0);
+        }
+        patterns
+    }
+
+    // Adds the pattern given as a diagram of the 9 intersections,
+    // row by row, with the move in the center.
+    pub fn add(&mut self, diagram: &str, weight: f32) {
+        let cells: Vec<char> = diagram.chars().collect();
+        assert!(cells.len() == 9 && cells[4] == '.', "invalid pattern: {}", diagram);
+        for colors in expand(cells.as_slice()).iter() {
+            for &swapped in [false, true].iter() {
+                let values: Vec<u16> = colors.iter()
+                    .map(|&v| if swapped { swap(v) } else { v })
+                    .collect();
+                for &symmetry in Symmetry::all().iter() {
+                    self.weights.insert(encode(transform(values.as_slice(), symmetry).as_slice()), weight);
+                }
+            }
+        }
+    }
+
+    // Loads the weights from a file written by the pattern training.
+    pub fn from_path(path: Path) -> Patterns {
+        let contents = File::open(&path).read_to_string().unwrap();
+        let mut patterns = Patterns::new();
+        for line in contents.as_slice().lines() {
+            let parts: Vec<&str> = line.split(' ').collect();
+            if parts.len() != 2 {
+                continue;
+            }
+            match (Feature::from_name(parts[0]), parts[1].parse::<f32>()) {
+                (Some(feature), Some(gamma)) => {
+                    match feature {
+                        Feature::Pattern3x3(key) => for variant in features::variants_3x3(key).into_iter() {
+                            patterns.weights.insert(variant, gamma);
+                        },
+                        _ => {}
+                    }
+                    patterns.gammas.insert(feature, gamma);
+                },
+                _ => {}
+            }
+        }
+        patterns
+    }
+
+    pub fn weight(&self, board: &Board, coord: Coord, color: Color) -> Option<f32> {
+        self.weights.get(&key(board, coord, color)).map(|w| *w)
+    }
+
+    // The Bradley-Terry strength of the move, i.e. the product of
+    // the gammas of its features. Features without a gamma count as
+    // 1.
+    pub fn strength(&self, board: &Board, m: &Move) -> f32 {
+        features::features(board, m)
+            .iter()
+            .fold(1.0, |s, f| s * *self.gammas.get(f).unwrap_or(&1.0))
+    }
+
+    pub fn matches(&self, board: &Board, coord: Coord, color: Color) -> bool {
+        self.weights.contains_key(&key(board, coord, color))
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+}
+
+// The key of the 3x3 neighbourhood of coord for the given player to
+// move. Each of the 8 surrounding intersections takes two bits.
+pub fn key(board: &Board, coord: Coord, color: Color) -> u16 {
+    let mut values = Vec::with_capacity(9);
+    for dy in range(-1i16, 2) {
+        for dx in range(-1i16, 2) {
+            values.push(value_at(board, coord, color, dx, dy));
+        }
+    }
+    encode(values.as_slice())
+}
+
+fn value_at(board: &Board, coord: Coord, color: Color, dx: i16, dy: i16) -> u16 {
+    let col = coord.col as i16 + dx;
+    let row = coord.row as i16 + dy;
+    let c = Coord::new(col as u8, row as u8);
+    if col < 1 || row < 1 || !c.is_inside(board.size()) {
+        OFF_BOARD
+    } else if board.color(&c) == Empty {
+        EMPTY
+    } else if board.color(&c) == color {
+        OWN
+    } else {
+        OPPONENT
+    }
+}
+
+fn encode(values: &[u16]) -> u16 {
+    values.iter()
+        .enumerate()
+        .filter(|&(i, _)| i != 4)
+        .fold(0, |key, (_, &v)| (key << 2) | v)
+}
+
+// The inverse of encode, with an empty center.
+fn decode(key: u16) -> Vec<u16> {
+    let mut values: Vec<u16> = range(0us, 8).map(|i| (key >> (2 * (7 - i))) & 3).collect();
+    values.insert(4, EMPTY);
+    values
+}
+
+fn transform(values: &[u16], symmetry: Symmetry) -> Vec<u16> {
+    let mut result = values.to_vec();
+    for i in range(0us, 9) {
+        let (x, y) = symmetry.apply((i % 3) as u8, (i / 3) as u8, 3);
+        result[(y * 3 + x) as usize] = values[i];
+    }
+    result
+}
+
+fn swap(value: u16) -> u16 {
+    if value == OWN {
+        OPPONENT
+    } else if value == OPPONENT {
+        OWN
+    } else {
+        value
+    }
+}
+
+// All the concrete colorings the diagram stands for.
+fn expand(cells: &[char]) -> Vec<Vec<u16>> {
+    let mut result: Vec<Vec<u16>> = vec!(vec!());
+    for &cell in cells.iter() {
+        let options = match cell {
+            'X' => vec!(OWN),
+            'O' => vec!(OPPONENT),
+            '.' => vec!(EMPTY),
+            '#' => vec!(OFF_BOARD),
+            'x' => vec!(EMPTY, OPPONENT, OFF_BOARD),
+            'o' => vec!(EMPTY, OWN, OFF_BOARD),
+            '?' => vec!(EMPTY, OWN, OPPONENT, OFF_BOARD),
+            _   => panic!("invalid pattern cell: {}", cell)
+        };
+        let mut next = Vec::new();
+        for prefix in result.iter() {
+            for &v in options.iter() {
+                let mut colors = prefix.clone();
+                colors.push(v);
+                next.push(colors);
+            }
+        }
+        result = next;
+    }
+    result
+}
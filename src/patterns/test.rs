@@ -0,0 +1,198 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Pass;
+use board::Play;
+use board::White;
+use ruleset::Minimal;
+use sgf::Parser;
+use super::Patterns;
+use super::features::Feature;
+use super::features::canonical_3x3;
+use super::features::features;
+use super::key;
+use super::training::Trainer;
+
+fn hane_board<'a>() -> Board<'a> {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 4, 4));
+    b.play(Play(White, 5, 4));
+    b.play(Play(Black, 6, 4));
+    b
+}
+
+#[test]
+fn the_key_of_an_empty_neighbourhood_is_0() {
+    let b = Board::new(9, 6.5, Minimal);
+    assert_eq!(0, key(&b, Coord::new(5, 5), Black));
+}
+
+#[test]
+fn the_key_marks_intersections_off_the_board() {
+    let b = Board::new(9, 6.5, Minimal);
+    assert!(key(&b, Coord::new(1, 1), Black) != 0);
+    assert!(key(&b, Coord::new(1, 1), Black) != key(&b, Coord::new(1, 2), Black));
+}
+
+#[test]
+fn the_key_depends_on_the_player_to_move() {
+    let b = hane_board();
+    assert!(key(&b, Coord::new(5, 5), Black) != key(&b, Coord::new(5, 5), White));
+}
+
+#[test]
+fn an_empty_neighbourhood_doesnt_match_the_mogo_patterns() {
+    let b = Board::new(9, 6.5, Minimal);
+    assert!(!Patterns::mogo().matches(&b, Coord::new(5, 5), Black));
+}
+
+#[test]
+fn the_enclosing_hane_matches() {
+    let b = hane_board();
+    let patterns = Patterns::mogo();
+    assert!(patterns.matches(&b, Coord::new(5, 5), Black));
+}
+
+#[test]
+fn patterns_match_for_both_colors() {
+    let b = hane_board();
+    let patterns = Patterns::mogo();
+    assert!(patterns.matches(&b, Coord::new(5, 5), White));
+}
+
+#[test]
+fn patterns_match_in_all_orientations() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 4, 4));
+    b.play(Play(White, 4, 5));
+    b.play(Play(Black, 4, 6));
+    let mut patterns = Patterns::new();
+    patterns.add("XOX...???", 1.0);
+    assert!(patterns.matches(&b, Coord::new(5, 5), Black));
+}
+
+#[test]
+fn weight_returns_the_weight_of_the_matching_pattern() {
+    let b = hane_board();
+    let mut patterns = Patterns::new();
+    patterns.add("XOX...???", 2.5);
+    assert_eq!(Some(2.5), patterns.weight(&b, Coord::new(5, 5), Black));
+    assert_eq!(None, patterns.weight(&b, Coord::new(2, 2), Black));
+}
+
+#[test]
+fn rotated_patterns_have_the_same_canonical_key() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 4, 4));
+    b.play(Play(White, 4, 5));
+    b.play(Play(Black, 4, 6));
+    let rotated = key(&b, Coord::new(5, 5), Black);
+    let original = key(&hane_board(), Coord::new(5, 5), Black);
+    assert!(rotated != original);
+    assert_eq!(canonical_3x3(rotated), canonical_3x3(original));
+}
+
+#[test]
+fn from_path_loads_the_3x3_pattern_weights() {
+    let patterns = Patterns::from_path(Path::new("fixtures/patterns/weights.txt"));
+    assert_eq!(Some(2.5), patterns.weight(&hane_board(), Coord::new(5, 5), Black));
+    assert_eq!(4, patterns.len());
+}
+
+#[test]
+fn feature_names_can_be_read_back() {
+    let all = vec!(Feature::Pattern3x3(25), Feature::Diamond(1234), Feature::Capture,
+                   Feature::AtariEscape, Feature::Atari, Feature::SelfAtari, Feature::Line(2),
+                   Feature::Distance(3), Feature::PassMove);
+    for &feature in all.iter() {
+        assert_eq!(Some(feature), Feature::from_name(feature.name().as_slice()));
+    }
+    assert_eq!(None, Feature::from_name("foo"));
+}
+
+#[test]
+fn a_pass_only_has_the_pass_feature() {
+    let b = Board::new(9, 6.5, Minimal);
+    assert_eq!(vec!(Feature::PassMove), features(&b, &Pass(Black)));
+}
+
+#[test]
+fn capturing_has_the_capture_feature() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 4, 5));
+    b.play(Play(White, 5, 5));
+    b.play(Play(Black, 6, 5));
+    b.play(Play(White, 1, 1));
+    b.play(Play(Black, 5, 6));
+    b.play(Play(White, 1, 9));
+    let fs = features(&b, &Play(Black, 5, 4));
+    assert!(fs.contains(&Feature::Capture));
+    assert!(!fs.contains(&Feature::SelfAtari));
+}
+
+#[test]
+fn moves_on_the_edge_have_the_line_feature() {
+    let b = Board::new(9, 6.5, Minimal);
+    assert!(features(&b, &Play(Black, 1, 5)).contains(&Feature::Line(1)));
+    assert!(features(&b, &Play(Black, 3, 5)).contains(&Feature::Line(3)));
+}
+
+#[test]
+fn moves_next_to_the_last_move_have_the_distance_feature() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 5, 5));
+    assert!(features(&b, &Play(White, 5, 6)).contains(&Feature::Distance(2)));
+    assert!(features(&b, &Play(White, 6, 6)).contains(&Feature::Distance(3)));
+}
+
+#[test]
+fn the_trainer_adds_a_position_per_move() {
+    let mut trainer = Trainer::new();
+    trainer.add_game(&Parser::from_path(Path::new("fixtures/sgf/twomoves.sgf")));
+    assert_eq!(2, trainer.positions());
+}
+
+#[test]
+fn the_trainer_skips_illegal_moves() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 5, 5));
+    let mut trainer = Trainer::new();
+    trainer.add_position(&b, &Play(White, 5, 5));
+    assert_eq!(0, trainer.positions());
+}
+
+#[test]
+fn features_of_played_moves_get_higher_gammas() {
+    let b = Board::new(9, 6.5, Minimal);
+    let mut trainer = Trainer::new();
+    for _ in range(0us, 10) {
+        trainer.add_position(&b, &Play(Black, 3, 3));
+    }
+    let gammas = trainer.fit(10);
+    let gamma = |f: Feature| gammas.iter().find(|&&(g, _)| g == f).unwrap().1;
+    assert!(gamma(Feature::Line(3)) > 1.0);
+    assert!(gamma(Feature::Line(1)) < 1.0);
+}
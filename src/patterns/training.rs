@@ -0,0 +1,159 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Move;
+use sgf::Parser;
+use sgf::collection::load;
+use sgf::collection::sgf_files;
+use super::features::Feature;
+use super::features::features;
+
+use std::collections::HashMap;
+use std::io::fs::File;
+
+// One position of a training game: The features of all legal moves
+// and which of them was actually played.
+struct Competition {
+    candidates: Vec<Vec<usize>>,
+    winner:     usize
+}
+
+// Fits Bradley-Terry weights (gammas) to the features of the moves
+// played in a collection of games, using the minorization-maximization
+// algorithm from Rémi Coulom's "Computing Elo Ratings of Move
+// Patterns in the Game of Go". The strength of a move is the product
+// of the gammas of its features.
+pub struct Trainer {
+    competitions: Vec<Competition>,
+    features:     Vec<Feature>,
+    index:        HashMap<Feature, usize>
+}
+
+impl Trainer {
+    pub fn new() -> Trainer {
+        Trainer {
+            competitions: Vec::new(),
+            features:     Vec::new(),
+            index:        HashMap::new()
+        }
+    }
+
+    // Adds all positions of the game. Stops at the first move that
+    // can't be played, e.g. because of a superko violation.
+    pub fn add_game(&mut self, parser: &Parser) {
+        let mut game = match parser.initial_game() {
+            Ok(g)  => g,
+            Err(_) => return
+        };
+        for &m in parser.moves().iter() {
+            self.add_position(&game.board(), &m);
+            game = match game.play(m) {
+                Ok(g)  => g,
+                Err(_) => return
+            };
+        }
+    }
+
+    // Adds the position with the given move played in it. Positions
+    // where the move isn't one of the legal moves of the player to
+    // move are skipped.
+    pub fn add_position(&mut self, board: &Board, played: &Move) {
+        let moves = board.legal_moves();
+        let winner = match moves.iter().position(|m| m == played) {
+            Some(i) => i,
+            None    => return
+        };
+        let mut candidates = Vec::new();
+        for m in moves.iter() {
+            let indices = features(board, m).into_iter().map(|f| self.feature_index(f)).collect();
+            candidates.push(indices);
+        }
+        self.competitions.push(Competition { candidates: candidates, winner: winner });
+    }
+
+    pub fn positions(&self) -> usize {
+        self.competitions.len()
+    }
+
+    // Runs the given number of MM iterations, starting with all
+    // gammas at 1. Every feature gets a virtual win and a virtual
+    // loss against an opponent with a gamma of 1, so that features
+    // that were seen only a few times stay close to 1.
+    pub fn fit(&self, iterations: usize) -> Vec<(Feature, f32)> {
+        let n = self.features.len();
+        let mut gammas: Vec<f32> = range(0, n).map(|_| 1f32).collect();
+        let mut wins: Vec<f32> = range(0, n).map(|_| 0f32).collect();
+        for competition in self.competitions.iter() {
+            for &i in competition.candidates[competition.winner].iter() {
+                wins[i] += 1f32;
+            }
+        }
+        for _ in range(0, iterations) {
+            let mut denominators: Vec<f32> = range(0, n).map(|_| 0f32).collect();
+            for competition in self.competitions.iter() {
+                let strengths: Vec<f32> = competition.candidates
+                    .iter()
+                    .map(|c| c.iter().fold(1f32, |s, &i| s * gammas[i]))
+                    .collect();
+                let total = strengths.iter().fold(0f32, |sum, &s| sum + s);
+                for (candidate, &strength) in competition.candidates.iter().zip(strengths.iter()) {
+                    for &i in candidate.iter() {
+                        denominators[i] += strength / gammas[i] / total;
+                    }
+                }
+            }
+            gammas = range(0, n)
+                .map(|i| (wins[i] + 1f32) / (denominators[i] + 2f32 / (gammas[i] + 1f32)))
+                .collect();
+        }
+        self.features.iter().cloned().zip(gammas.into_iter()).collect()
+    }
+
+    fn feature_index(&mut self, feature: Feature) -> usize {
+        match self.index.get(&feature) {
+            Some(&i) => return i,
+            None     => {}
+        }
+        let i = self.features.len();
+        self.features.push(feature);
+        self.index.insert(feature, i);
+        i
+    }
+}
+
+// Trains the weights on all SGF files in the directory and writes
+// them to the output file, one feature per line.
+pub fn train(directory: Path, output: Path, iterations: usize) {
+    let mut trainer = Trainer::new();
+    for path in sgf_files(&directory).iter() {
+        println!("Reading {}", path.display());
+        match load(path) {
+            Some(parser) => trainer.add_game(&parser),
+            None         => {}
+        }
+    }
+    println!("Fitting {} positions", trainer.positions());
+    let mut file = File::create(&output).unwrap();
+    for &(feature, gamma) in trainer.fit(iterations).iter() {
+        file.write_line(format!("{} {}", feature.name(), gamma).as_slice()).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Board;
+use board::Empty;
+use board::Play;
+use board::White;
+use ruleset::AnySizeTrompTaylor;
+use ruleset::KgsChinese;
+use ruleset::Ruleset;
+
+use core::fmt;
+use std::io::fs::File;
+
+mod test;
+
+// The number of move sequences of a given length and how many of
+// them end with a capture, a move that creates a ko, a suicide or a
+// pass.
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub struct Counts {
+    pub nodes:    u64,
+    pub captures: u64,
+    pub kos:      u64,
+    pub suicides: u64,
+    pub passes:   u64
+}
+
+impl Counts {
+    pub fn new() -> Counts {
+        Counts { nodes: 0, captures: 0, kos: 0, suicides: 0, passes: 0 }
+    }
+}
+
+impl fmt::String for Counts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = format!("{} {} {} {} {}", self.nodes, self.captures, self.kos, self.suicides, self.passes);
+        s.fmt(f)
+    }
+}
+
+// Counts all sequences of legal moves of the given depth. Every move
+// that legal_moves returns has to be playable.
+pub fn perft(board: &Board, depth: u8) -> Counts {
+    let mut counts = Counts::new();
+    count(board, depth, &mut counts);
+    counts
+}
+
+fn count(board: &Board, depth: u8, counts: &mut Counts) {
+    if depth == 0 || board.is_game_over() {
+        return;
+    }
+    for &m in board.legal_moves().iter() {
+        let mut b = board.clone();
+        match b.play(m) {
+            Ok(_)  => {},
+            Err(e) => panic!("The legal move {:?} can't be played: {:?}\n{}", m, e, board.as_string())
+        }
+        if depth > 1 {
+            count(&b, depth - 1, counts);
+            continue;
+        }
+        counts.nodes += 1;
+        if m.is_pass() {
+            counts.passes += 1;
+        } else {
+            if b.adv_stones_removed().len() > 0 {
+                counts.captures += 1;
+            }
+            if b.color(&m.coord()) == Empty {
+                counts.suicides += 1;
+            }
+        }
+        if b.ko().is_some() {
+            counts.kos += 1;
+        }
+    }
+}
+
+// The named positions the reference numbers are for. The ko
+// position has a ko that black can take right away.
+pub fn position<'a>(name: &str) -> Option<Board<'a>> {
+    let parts: Vec<&str> = name.split('-').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let ruleset = match parts[2] {
+        "kgs" => KgsChinese,
+        "tt"  => AnySizeTrompTaylor,
+        _     => return None
+    };
+    match (parts[0], parts[1]) {
+        ("empty", "3x3") => Some(Board::new(3, 6.5, ruleset)),
+        ("ko", "4x4")    => Some(ko_position(ruleset)),
+        _                => None
+    }
+}
+
+fn ko_position<'a>(ruleset: Ruleset) -> Board<'a> {
+    let mut board = Board::new(4, 6.5, ruleset);
+    let moves = [Play(Black, 2, 4), Play(White, 3, 4), Play(Black, 1, 3), Play(White, 2, 3),
+                 Play(Black, 2, 2), Play(White, 4, 3), Play(Black, 1, 1), Play(White, 3, 2)];
+    for &m in moves.iter() {
+        board.play(m).unwrap();
+    }
+    board
+}
+
+// Compares the counts with the reference numbers in the file, one
+// "position depth nodes captures kos suicides passes" line each.
+// Returns the lines that don't match, after printing all results.
+pub fn check(path: Path, max_depth: u8) -> Vec<String> {
+    let contents = File::open(&path).read_to_string().unwrap();
+    let mut failures = Vec::new();
+    for line in contents.as_slice().lines().filter(|l| !l.starts_with("#") && l.trim().len() > 0) {
+        let parts: Vec<&str> = line.split(' ').collect();
+        let depth = parts[1].parse::<u8>().unwrap();
+        if depth > max_depth {
+            continue;
+        }
+        let board = match position(parts[0]) {
+            Some(board) => board,
+            None        => panic!("Unknown perft position: {}", parts[0])
+        };
+        let expected = parts.slice_from(2).connect(" ");
+        let counts = format!("{}", perft(&board, depth));
+        if counts == expected {
+            println!("{} {}: {} ok", parts[0], depth, counts);
+        } else {
+            println!("{} {}: {} expected {}", parts[0], depth, counts, expected);
+            failures.push(String::from_str(line));
+        }
+    }
+    failures
+}
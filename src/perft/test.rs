@@ -0,0 +1,53 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use super::Counts;
+use super::check;
+use super::perft;
+use super::position;
+
+#[test]
+fn the_empty_board_has_one_move_per_point_and_a_pass() {
+    let board = position("empty-3x3-kgs").unwrap();
+    assert_eq!(Counts { nodes: 10, captures: 0, kos: 0, suicides: 0, passes: 1 }, perft(&board, 1));
+}
+
+#[test]
+fn taking_the_ko_is_a_capture_that_creates_a_ko() {
+    let board = position("ko-4x4-kgs").unwrap();
+    let counts = perft(&board, 1);
+    assert_eq!(1, counts.captures);
+    assert_eq!(1, counts.kos);
+}
+
+#[test]
+fn unknown_positions_are_rejected() {
+    assert!(position("empty-5x5-kgs").is_none());
+    assert!(position("nonsense").is_none());
+}
+
+#[test]
+fn the_counts_match_the_reference_numbers() {
+    let failures = check(Path::new("fixtures/perft/reference.txt"), 5);
+    assert_eq!(Vec::<String>::new(), failures);
+}
@@ -20,31 +20,132 @@
  ************************************************************************/
 use board::Board;
 use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Move;
+use board::Play;
+use engine::McConfig;
+use patterns::Patterns;
+use tactics::ladder;
+use tactics::semeai;
 
-use std::rand::random;
+use std::rand::Rng;
+use std::rand::thread_rng;
+use std::rc::Rc;
 
 mod test;
 
+pub struct PlayoutResult {
+    moves:  Vec<Move>,
+    winner: Color
+}
+
+impl PlayoutResult {
+    // The moves played during the playout, in order
+    pub fn moves(&self) -> &Vec<Move> {
+        &self.moves
+    }
+
+    pub fn winner(&self) -> Color {
+        self.winner
+    }
+}
+
 pub struct Playout<'a> {
-    board: Board<'a>
+    board:    Board<'a>,
+    config:   McConfig,
+    patterns: Option<Rc<Patterns>>
 }
 
 impl<'a> Playout<'a> {
     pub fn new(b: Board) -> Playout {
-        Playout { board: b }
+        Playout { board: b, config: McConfig::new(), patterns: None }
+    }
+
+    // The config sets how often and which pattern moves are played.
+    pub fn with_patterns(b: Board, patterns: Rc<Patterns>, config: McConfig) -> Playout {
+        Playout { board: b, config: config, patterns: Some(patterns) }
+    }
+
+    pub fn run(&self) -> PlayoutResult {
+        self.run_with_rng(&mut thread_rng())
     }
 
-    pub fn run(&self) -> Color {
+    // Runs the playout with the given random number generator, so
+    // that a seeded generator always leads to the same playout.
+    pub fn run_with_rng<R: Rng>(&self, rng: &mut R) -> PlayoutResult {
         let mut board = self.board.clone();
-        let max_moves = board.size() * board.size() * 3;
-        let mut move_count = 0;
-        while !board.is_game_over() && move_count < max_moves {
-            let moves = board.legal_moves();
-            let m = moves[random::<usize>() % moves.len()];
+        let size = board.size() as usize;
+        let max_moves = size * size * 3;
+        let mut moves = Vec::new();
+        while !board.is_game_over() && moves.len() < max_moves {
+            let m = self.select_move(&board, rng);
             board.play(m);
-            move_count += 1;
+            moves.push(m);
+        }
+        PlayoutResult { moves: moves, winner: board.winner() }
+    }
+
+    // Picks a random legal move, but doesn't waste moves on running
+    // out dead ladders or chasing broken ones, and doesn't throw away
+    // capturing races that are won. Moves around the last move that
+    // match a pattern are usually played first.
+    fn select_move<R: Rng>(&self, board: &Board, rng: &mut R) -> Move {
+        let mut moves = self.pattern_moves(board);
+        let rate = self.config.playout_pattern_rate;
+        if moves.len() > 0 && (rate >= 1.0 || rng.gen::<f32>() < rate) {
+            let m = moves[rng.gen_range(0, moves.len())];
+            if !self.is_bad_move(board, &m) {
+                return m;
+            }
+        }
+        moves = board.legal_moves();
+        loop {
+            let i = rng.gen_range(0, moves.len());
+            let m = moves[i];
+            if moves.len() == 1 || !self.is_bad_move(board, &m) {
+                return m;
+            }
+            moves.swap_remove(i);
+        }
+    }
+
+    fn is_bad_move(&self, board: &Board, m: &Move) -> bool {
+        ladder::is_wasted_move(board, m) || semeai::is_losing_move(board, m)
+    }
+
+    // The legal moves in the 3x3 area around the last move that
+    // match one of the patterns with at least the configured weight.
+    fn pattern_moves(&self, board: &Board) -> Vec<Move> {
+        let patterns = match self.patterns {
+            Some(ref p) => p,
+            None        => return vec!()
+        };
+        let last = match board.last_move() {
+            Some(m) if !m.is_pass() => m.coord(),
+            _                       => return vec!()
+        };
+        let color = board.next_player();
+        let mut moves = Vec::new();
+        for dx in range(-1i16, 2) {
+            for dy in range(-1i16, 2) {
+                let col = last.col as i16 + dx;
+                let row = last.row as i16 + dy;
+                let coord = Coord::new(col as u8, row as u8);
+                if col < 1 || row < 1 || !coord.is_inside(board.size()) || board.color(&coord) != Empty {
+                    continue;
+                }
+                let m = Play(color, coord.col, coord.row);
+                let good = match patterns.weight(board, coord, color) {
+                    Some(weight) => weight >= self.config.playout_pattern_min,
+                    None         => false
+                };
+                if good && board.is_legal(m).is_ok() {
+                    moves.push(m);
+                }
+            }
         }
-        board.winner()
+        moves
     }
 
 }
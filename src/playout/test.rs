@@ -22,6 +22,9 @@
 use playout::Playout;
 use game::Game;
 use ruleset::KgsChinese;
+
+use std::rand::SeedableRng;
+use std::rand::XorShiftRng;
 use test::Bencher;
 
 #[bench]
@@ -50,3 +53,14 @@ fn bench_19x19_playout_speed(b: &mut Bencher) {
 
     b.iter(|| {playout_engine.run()})
 }
+
+#[test]
+fn the_same_seed_leads_to_the_same_playout() {
+    let game = Game::new(9, 6.5, KgsChinese);
+    let playout = Playout::new(game.board());
+    let mut first_rng: XorShiftRng = SeedableRng::from_seed([1, 2, 3, 4]);
+    let mut second_rng: XorShiftRng = SeedableRng::from_seed([1, 2, 3, 4]);
+    let first = playout.run_with_rng(&mut first_rng);
+    let second = playout.run_with_rng(&mut second_rng);
+    assert_eq!(first.moves(), second.moves());
+}
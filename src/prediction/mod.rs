@@ -0,0 +1,160 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Move;
+use engine::Engine;
+use game::Game;
+use sgf::Parser;
+use sgf::collection::load;
+use sgf::collection::sgf_files;
+
+mod test;
+
+// The last move of the opening and of the middle game. Everything
+// after that is the endgame.
+static OPENING_END:     usize = 40;
+static MIDDLE_GAME_END: usize = 150;
+
+static PHASES: [&'static str; 3] = ["opening", "middle game", "endgame"];
+
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub struct Accuracy {
+    positions: usize,
+    top1:      usize,
+    top5:      usize
+}
+
+impl Accuracy {
+    pub fn new() -> Accuracy {
+        Accuracy { positions: 0, top1: 0, top5: 0 }
+    }
+
+    // Records a position where the actual move had the given rank
+    // (starting at 0) among the engine's moves.
+    pub fn add(&mut self, rank: Option<usize>) {
+        self.positions += 1;
+        match rank {
+            Some(0)             => { self.top1 += 1; self.top5 += 1; },
+            Some(r) if r < 5    => self.top5 += 1,
+            _                   => {}
+        }
+    }
+
+    pub fn positions(&self) -> usize {
+        self.positions
+    }
+
+    pub fn top1(&self) -> f32 {
+        self.ratio(self.top1)
+    }
+
+    pub fn top5(&self) -> f32 {
+        self.ratio(self.top5)
+    }
+
+    fn ratio(&self, n: usize) -> f32 {
+        if self.positions == 0 {
+            0f32
+        } else {
+            n as f32 / self.positions as f32
+        }
+    }
+}
+
+// Measures how often the moves an engine ranks highest agree with
+// the moves played in a collection of games.
+pub struct Prediction {
+    phases: Vec<Accuracy>
+}
+
+impl Prediction {
+    pub fn new() -> Prediction {
+        Prediction { phases: PHASES.iter().map(|_| Accuracy::new()).collect() }
+    }
+
+    // Replays the game and asks the engine for its moves in every
+    // position. Stops at the first move that can't be played.
+    pub fn add_game(&mut self, engine: &Engine, parser: &Parser) {
+        let mut game = match parser.initial_game() {
+            Ok(g)  => g,
+            Err(_) => return
+        };
+        for (i, &m) in parser.moves().iter().enumerate() {
+            self.add_position(engine, &game, &m, i + 1);
+            game = match game.play(m) {
+                Ok(g)  => g,
+                Err(_) => return
+            };
+        }
+    }
+
+    pub fn add_position(&mut self, engine: &Engine, game: &Game, played: &Move, move_number: usize) {
+        let moves = engine.ranked_moves(*played.color(), game);
+        let rank = moves.iter().position(|m| m == played);
+        self.phases[phase(move_number)].add(rank);
+    }
+
+    pub fn phase(&self, name: &str) -> Accuracy {
+        let i = PHASES.iter().position(|&p| p == name).unwrap();
+        self.phases[i]
+    }
+
+    pub fn total(&self) -> Accuracy {
+        self.phases.iter().fold(Accuracy::new(), |total, a| Accuracy {
+            positions: total.positions + a.positions,
+            top1:      total.top1 + a.top1,
+            top5:      total.top5 + a.top5
+        })
+    }
+
+    pub fn report(&self) -> String {
+        let mut lines = vec!(format!("{:<12} {:>9} {:>7} {:>7}", "phase", "positions", "top-1", "top-5"));
+        let rows = PHASES.iter().map(|&p| (p, self.phase(p))).chain(Some(("total", self.total())).into_iter());
+        for (name, a) in rows {
+            lines.push(format!("{:<12} {:>9} {:>6.1}% {:>6.1}%", name, a.positions(), a.top1() * 100.0, a.top5() * 100.0));
+        }
+        lines.connect("\n")
+    }
+}
+
+fn phase(move_number: usize) -> usize {
+    if move_number <= OPENING_END {
+        0
+    } else if move_number <= MIDDLE_GAME_END {
+        1
+    } else {
+        2
+    }
+}
+
+// Runs the engine on all SGF files in the directory and prints the
+// agreement with the moves played.
+pub fn run(engine: Box<Engine>, directory: Path) {
+    let mut prediction = Prediction::new();
+    for path in sgf_files(&directory).iter() {
+        println!("Reading {}", path.display());
+        match load(path) {
+            Some(parser) => prediction.add_game(&*engine, &parser),
+            None         => {}
+        }
+    }
+    println!("{}", prediction.report());
+}
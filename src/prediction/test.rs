@@ -0,0 +1,100 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Play;
+use board::White;
+use engine::Engine;
+use engine::PolicyEngine;
+use game::Game;
+use patterns::Patterns;
+use ruleset::Minimal;
+use sgf::Parser;
+use super::Accuracy;
+use super::Prediction;
+
+#[test]
+fn a_move_ranked_first_counts_for_top1_and_top5() {
+    let mut a = Accuracy::new();
+    a.add(Some(0));
+    assert_eq!(1f32, a.top1());
+    assert_eq!(1f32, a.top5());
+}
+
+#[test]
+fn a_move_ranked_third_only_counts_for_top5() {
+    let mut a = Accuracy::new();
+    a.add(Some(2));
+    a.add(None);
+    assert_eq!(2, a.positions());
+    assert_eq!(0f32, a.top1());
+    assert_eq!(0.5f32, a.top5());
+}
+
+#[test]
+fn positions_are_split_by_game_phase() {
+    let engine = PolicyEngine::new(Patterns::new());
+    let game = Game::new(9, 6.5, Minimal);
+    let mut prediction = Prediction::new();
+    prediction.add_position(&engine, &game, &Play(Black, 5, 5), 1);
+    prediction.add_position(&engine, &game, &Play(Black, 5, 5), 100);
+    prediction.add_position(&engine, &game, &Play(Black, 5, 5), 200);
+    prediction.add_position(&engine, &game, &Play(Black, 5, 5), 201);
+    assert_eq!(1, prediction.phase("opening").positions());
+    assert_eq!(1, prediction.phase("middle game").positions());
+    assert_eq!(2, prediction.phase("endgame").positions());
+    assert_eq!(4, prediction.total().positions());
+}
+
+#[test]
+fn add_game_adds_all_moves() {
+    let engine = PolicyEngine::new(Patterns::new());
+    let mut prediction = Prediction::new();
+    prediction.add_game(&engine, &Parser::from_path(Path::new("fixtures/sgf/twomoves.sgf")));
+    assert_eq!(2, prediction.total().positions());
+}
+
+#[test]
+fn the_report_contains_all_phases() {
+    let report = Prediction::new().report();
+    assert!(report.as_slice().contains("opening"));
+    assert!(report.as_slice().contains("middle game"));
+    assert!(report.as_slice().contains("endgame"));
+    assert!(report.as_slice().contains("total"));
+}
+
+#[test]
+fn the_policy_engine_ranks_all_legal_moves() {
+    let engine = PolicyEngine::new(Patterns::new());
+    let game = Game::new(9, 6.5, Minimal);
+    assert_eq!(82, engine.ranked_moves(Black, &game).len());
+}
+
+#[test]
+fn the_policy_engine_ranks_the_moves_of_the_given_color() {
+    let engine = PolicyEngine::new(Patterns::new());
+    let game = Game::new(9, 6.5, Minimal);
+    let moves = engine.ranked_moves(White, &game);
+    assert_eq!(82, moves.len());
+    assert!(moves.iter().all(|m| *m.color() == White));
+}
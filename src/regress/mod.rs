@@ -0,0 +1,169 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use gtp::GTPInterpreter;
+
+use regex::Regex;
+use std::io::fs::File;
+
+mod test;
+
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    UnexpectedSuccess,
+    ExpectedFailure
+}
+
+// The expected response from a "#?" line, e.g. [C3|D4]. The pattern
+// is a regular expression that has to match the whole response, a !
+// negates it and a trailing * marks a known failure.
+#[derive(Show)]
+pub struct Expectation {
+    text:          String,
+    pattern:       String,
+    negated:       bool,
+    known_failure: bool
+}
+
+impl Expectation {
+    pub fn parse(line: &str) -> Option<Expectation> {
+        let re = regex!(r"^#\?\s*\[(!?)(.*)\](\*?)\s*$");
+        re.captures(line).map(|caps| {
+            Expectation {
+                text:          String::from_str(line.slice_from(2).trim()),
+                pattern:       String::from_str(caps.at(2).unwrap()),
+                negated:       caps.at(1).unwrap() == "!",
+                known_failure: caps.at(3).unwrap() == "*"
+            }
+        })
+    }
+
+    pub fn matches(&self, response: &Result<String, String>) -> bool {
+        let re = match Regex::new(format!("(?i)^({})$", self.pattern).as_slice()) {
+            Ok(re) => re,
+            Err(_) => return false
+        };
+        match *response {
+            Ok(ref s) => re.is_match(s.as_slice().trim()) != self.negated,
+            Err(_)    => false
+        }
+    }
+
+    pub fn outcome(&self, response: &Result<String, String>) -> Outcome {
+        match (self.matches(response), self.known_failure) {
+            (true, false)  => Outcome::Passed,
+            (false, false) => Outcome::Failed,
+            (true, true)   => Outcome::UnexpectedSuccess,
+            (false, true)  => Outcome::ExpectedFailure
+        }
+    }
+}
+
+pub struct Test {
+    pub id:          String,
+    pub command:     String,
+    pub response:    Result<String, String>,
+    pub expectation: Expectation,
+    pub outcome:     Outcome
+}
+
+// Runs the commands of a .tst file and checks the responses of the
+// commands that are followed by a "#?" line. The files of loadsgf
+// commands are relative to the directory of the test file.
+pub fn run_file(interpreter: &mut GTPInterpreter, path: &Path) -> Vec<Test> {
+    let contents = File::open(path).read_to_string().unwrap();
+    let numbered = regex!(r"^(\d+)\s+(.*)$");
+    let mut tests = Vec::new();
+    let mut last: Option<(String, String, Result<String, String>)> = None;
+    for line in contents.as_slice().lines().map(|l| l.trim()) {
+        if line.starts_with("#?") {
+            match (last.take(), Expectation::parse(line)) {
+                (Some((id, command, response)), Some(expectation)) => {
+                    let outcome = expectation.outcome(&response);
+                    tests.push(Test {
+                        id:          id,
+                        command:     command,
+                        response:    response,
+                        expectation: expectation,
+                        outcome:     outcome
+                    });
+                },
+                _ => panic!("Invalid expectation in {}: {}", path.display(), line)
+            }
+        } else if line.starts_with("#") || line.len() == 0 {
+            continue;
+        } else {
+            let (id, command) = match numbered.captures(line) {
+                Some(caps) => (String::from_str(caps.at(1).unwrap()), String::from_str(caps.at(2).unwrap())),
+                None       => (String::new(), String::from_str(line))
+            };
+            let command = relative_to(path, command.as_slice());
+            let response = interpreter.read(command.as_slice()).response();
+            last = Some((id, command, response));
+        }
+    }
+    tests
+}
+
+fn relative_to(path: &Path, command: &str) -> String {
+    let args: Vec<&str> = command.split(' ').filter(|s| s.len() > 0).collect();
+    if args.len() < 2 || args[0] != "loadsgf" || Path::new(args[1]).is_absolute() {
+        return String::from_str(command);
+    }
+    let file = path.dir_path().join(args[1]);
+    let mut rewritten = vec!(String::from_str("loadsgf"), file.display().to_string());
+    rewritten.extend(args.slice_from(2).iter().map(|s| String::from_str(*s)));
+    rewritten.connect(" ")
+}
+
+// Runs all test files with the same interpreter, like gogui-regress
+// does, and prints the tests that didn't have the expected outcome.
+// Returns false if any test failed.
+pub fn run(interpreter: &mut GTPInterpreter, files: &Vec<Path>) -> bool {
+    let mut counts = [0us; 4];
+    for path in files.iter() {
+        println!("{}", path.display());
+        for test in run_file(interpreter, path).iter() {
+            let index = match test.outcome {
+                Outcome::Passed            => 0,
+                Outcome::Failed            => 1,
+                Outcome::UnexpectedSuccess => 2,
+                Outcome::ExpectedFailure   => 3
+            };
+            counts[index] += 1;
+            let label = match test.outcome {
+                Outcome::Failed            => "FAIL",
+                Outcome::UnexpectedSuccess => "PASS",
+                _                          => continue
+            };
+            let response = match test.response {
+                Ok(ref s)  => s.clone(),
+                Err(ref s) => format!("? {}", s)
+            };
+            println!("  {} {} {}: got {} expected {}", test.id, label, test.command, response, test.expectation.text);
+        }
+    }
+    println!("{} passed, {} failed, {} unexpected successes, {} expected failures",
+             counts[0], counts[1], counts[2], counts[3]);
+    counts[1] == 0
+}
@@ -0,0 +1,95 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use engine::RandomEngine;
+use gtp::GTPInterpreter;
+use super::Expectation;
+use super::Outcome;
+use super::relative_to;
+use super::run_file;
+
+fn expectation(line: &str) -> Expectation {
+    Expectation::parse(line).unwrap()
+}
+
+fn ok(s: &str) -> Result<String, String> {
+    Ok(String::from_str(s))
+}
+
+#[test]
+fn any_of_the_alternatives_passes() {
+    let e = expectation("#? [C3|D4]");
+    assert_eq!(Outcome::Passed, e.outcome(&ok("C3")));
+    assert_eq!(Outcome::Passed, e.outcome(&ok("d4")));
+    assert_eq!(Outcome::Failed, e.outcome(&ok("C4")));
+}
+
+#[test]
+fn the_pattern_has_to_match_the_whole_response() {
+    let e = expectation("#? [C3]");
+    assert_eq!(Outcome::Failed, e.outcome(&ok("C31")));
+}
+
+#[test]
+fn a_negated_pattern_passes_if_it_doesnt_match() {
+    let e = expectation("#? [!pass]");
+    assert_eq!(Outcome::Passed, e.outcome(&ok("C3")));
+    assert_eq!(Outcome::Failed, e.outcome(&ok("PASS")));
+}
+
+#[test]
+fn known_failures_are_reported_as_unexpected_successes() {
+    let e = expectation("#? [C3]*");
+    assert_eq!(Outcome::UnexpectedSuccess, e.outcome(&ok("C3")));
+    assert_eq!(Outcome::ExpectedFailure, e.outcome(&ok("D4")));
+}
+
+#[test]
+fn failure_responses_never_match() {
+    let e = expectation("#? [!C3]");
+    assert_eq!(Outcome::Failed, e.outcome(&Err(String::from_str("unknown command"))));
+}
+
+#[test]
+fn invalid_expectations_are_rejected() {
+    assert!(Expectation::parse("#? C3").is_none());
+}
+
+#[test]
+fn the_gtp_regression_tests_pass() {
+    let mut interpreter = GTPInterpreter::new(Box::new(RandomEngine::new()));
+    let tests = run_file(&mut interpreter, &Path::new("fixtures/regress/gtp.tst"));
+    assert_eq!(4, tests.len());
+    assert_eq!(vec!("1", "2", "3", "4"), tests.iter().map(|t| t.id.as_slice()).collect::<Vec<&str>>());
+    for test in tests.iter() {
+        assert_eq!(Outcome::Passed, test.outcome);
+    }
+}
+
+#[test]
+fn loadsgf_is_relative_to_the_test_file() {
+    let path = Path::new("fixtures/regress/gtp.tst");
+    assert_eq!("loadsgf fixtures/sgf/twomoves.sgf 2", relative_to(&path, "loadsgf ../sgf/twomoves.sgf 2").as_slice());
+    assert_eq!("loadsgf /tmp/game.sgf", relative_to(&path, "loadsgf /tmp/game.sgf").as_slice());
+    assert_eq!("genmove b", relative_to(&path, "genmove b").as_slice());
+}
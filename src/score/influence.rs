@@ -0,0 +1,141 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::White;
+
+use std::cmp;
+use std::num::Float;
+
+// Bouzy's dilation/erosion algorithm. Stones start with a value of
+// +128 (black) and -128 (white). Dilations spread the influence to
+// the neighbouring intersections, erosions then remove it again
+// from everything that isn't securely enclosed. The 5/21 variant is
+// a conservative estimate of the territory.
+pub struct Influence {
+    size:   u8,
+    values: Vec<i32>,
+}
+
+impl Influence {
+    pub fn new(board: &Board) -> Influence {
+        Influence::with_operations(board, 5, 21)
+    }
+
+    pub fn with_operations(board: &Board, dilations: usize, erosions: usize) -> Influence {
+        let values = board.points().iter().map(|p| match p.color {
+            Black => 128,
+            White => -128,
+            Empty => 0
+        }).collect();
+        let mut influence = Influence { size: board.size(), values: values };
+        for _ in range(0, dilations) {
+            influence.dilate(board);
+        }
+        for _ in range(0, erosions) {
+            influence.erode(board);
+        }
+        influence
+    }
+
+    pub fn value(&self, c: &Coord) -> i32 {
+        self.values[c.to_index(self.size)]
+    }
+
+    pub fn owner(&self, c: &Coord) -> Color {
+        let value = self.value(c);
+        if value > 0 {
+            Black
+        } else if value < 0 {
+            White
+        } else {
+            Empty
+        }
+    }
+
+    // The ownership of each intersection (by index) between 1.0
+    // (black) and -1.0 (white).
+    pub fn ownership(&self) -> Vec<f32> {
+        self.values.iter().map(|&v| (v as f32 / 64.0).max(-1.0).min(1.0)).collect()
+    }
+
+    // Area score of the estimated territory, positive if black is
+    // ahead.
+    pub fn estimated_score(&self, komi: f32) -> f32 {
+        let black = self.values.iter().filter(|&&v| v > 0).count();
+        let white = self.values.iter().filter(|&&v| v < 0).count();
+        black as f32 - white as f32 - komi
+    }
+
+    // The estimated score as text, e.g. "B+3.5", or "0" for a draw.
+    pub fn estimate(&self, komi: f32) -> String {
+        let score = self.estimated_score(komi);
+        if score > 0.0 {
+            format!("B+{}", score)
+        } else if score < 0.0 {
+            format!("W+{}", -score)
+        } else {
+            String::from_str("0")
+        }
+    }
+
+    fn neighbour_values(&self, board: &Board, i: usize) -> Vec<i32> {
+        board.neighbours(Coord::from_index(i, self.size))
+            .iter()
+            .map(|c| self.value(c))
+            .collect()
+    }
+
+    fn dilate(&mut self, board: &Board) {
+        let mut values = self.values.clone();
+        for i in range(0, self.values.len()) {
+            let value = self.values[i];
+            let neighbours = self.neighbour_values(board, i);
+            if value >= 0 && neighbours.iter().all(|&n| n >= 0) {
+                values[i] += neighbours.iter().filter(|&&n| n > 0).count() as i32;
+            }
+            if value <= 0 && neighbours.iter().all(|&n| n <= 0) {
+                values[i] -= neighbours.iter().filter(|&&n| n < 0).count() as i32;
+            }
+        }
+        self.values = values;
+    }
+
+    fn erode(&mut self, board: &Board) {
+        let mut values = self.values.clone();
+        for i in range(0, self.values.len()) {
+            let value = self.values[i];
+            let neighbours = self.neighbour_values(board, i);
+            if value > 0 {
+                let count = neighbours.iter().filter(|&&n| n <= 0).count() as i32;
+                values[i] = cmp::max(0, value - count);
+            } else if value < 0 {
+                let count = neighbours.iter().filter(|&&n| n >= 0).count() as i32;
+                values[i] = cmp::min(0, value + count);
+            }
+        }
+        self.values = values;
+    }
+}
@@ -26,15 +26,26 @@ use board::Color;
 use board::Coord;
 use board::Empty;
 use board::White;
+pub use self::influence::Influence;
 use self::territory::Territory;
+use tactics::benson::PassAlive;
+use tactics::seki::seki_chains;
 
 use core::fmt::String;
 use std::fmt;
 use std::num::Float;
 
+mod influence;
 mod territory;
 mod test;
 
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub enum FinalStatus {
+    Alive,
+    Dead,
+    Seki
+}
+
 pub struct Score {
     black_stones: usize,
     komi:         f32,
@@ -50,6 +61,17 @@ impl Score {
     // Store a reference to the Board in Score and compute the score
     // in an instance method.
     pub fn new(board: &Board) -> Score {
+        let (bs, ws) = Score::score_area(board);
+        Score {
+            black_stones: bs,
+            komi:         board.komi(),
+            white_stones: ws
+        }
+    }
+
+    // Unlike new this also looks for dead stones and seki, which is
+    // too slow for the end of every playout.
+    pub fn final_score(board: &Board) -> Score {
         let (bs, ws) = Score::score_tt(board);
         Score {
             black_stones: bs,
@@ -81,21 +103,62 @@ impl Score {
         (self.black_stones as f32 - (self.white_stones as f32 + self.komi)).abs()
     }
 
+    // The stones with the given status grouped by chain.
+    pub fn final_status_list(board: &Board, status: FinalStatus) -> Vec<Vec<Coord>> {
+        let pass_alive = PassAlive::new(board);
+        let seki = seki_chains(board);
+        board.chains()
+            .iter()
+            .filter(|chain| Score::stone_status(board, &pass_alive, &seki, &chain.coords()[0]) == status)
+            .map(|chain| chain.coords().clone())
+            .collect()
+    }
+
+    // Stones inside of pass-alive territory are dead, unless they
+    // are in seki.
+    fn stone_status(board: &Board, pass_alive: &PassAlive, seki: &Vec<usize>, c: &Coord) -> FinalStatus {
+        if seki.contains(&board.chain_id(c)) {
+            FinalStatus::Seki
+        } else if pass_alive.owner(c) == board.color(c).opposite() {
+            FinalStatus::Dead
+        } else {
+            FinalStatus::Alive
+        }
+    }
+
+    // All stones are alive, so this only counts the stones and the
+    // empty regions that border a single color.
+    fn score_area(board: &Board) -> (usize, usize) {
+        let black_stones = board.points().iter().filter(|p| p.color == Black).count();
+        let white_stones = board.points().iter().filter(|p| p.color == White).count();
+        let (black_territory, white_territory) = Score::count_territory(board, None);
+        (black_stones + black_territory, white_stones + white_territory)
+    }
+
+    // Dead stones count for the opponent.
     fn score_tt(board: &Board) -> (usize, usize) {
-        let (black_stones, white_stones) = Score::count_stones(board);
-        let (black_territory, white_territory) = Score::count_territory(board);
+        let pass_alive = PassAlive::new(board);
+        let seki = seki_chains(board);
+        let (black_stones, white_stones) = Score::count_stones(board, &pass_alive, &seki);
+        let (black_territory, white_territory) = Score::count_territory(board, Some(&pass_alive));
         let black_score = black_stones + black_territory;
         let white_score = white_stones + white_territory;
         (black_score, white_score)
     }
 
-    fn count_territory(board: &Board) -> (usize, usize) {
+    fn count_territory(board: &Board, pass_alive: Option<&PassAlive>) -> (usize, usize) {
         let mut black = 0;
         let mut white = 0;
         let mut empty_intersections = board.vacant().clone();
         while empty_intersections.len() > 0 {
-            let territory = Score::build_territory_chain(empty_intersections[0], board);
-            match territory.color() {
+            let first = empty_intersections[0];
+            let territory = Score::build_territory_chain(first, board);
+            let owner = match pass_alive.map(|pa| pa.owner(&first)) {
+                Some(Black) => Black,
+                Some(White) => White,
+                _           => territory.color()
+            };
+            match owner {
                 Black => black += territory.size(),
                 White => white += territory.size(),
                 Empty => () // This territory is not enclosed by a single color
@@ -108,11 +171,19 @@ impl Score {
         (black, white)
     }
 
-    fn count_stones(board: &Board) -> (usize, usize) {
+    fn count_stones(board: &Board, pass_alive: &PassAlive, seki: &Vec<usize>) -> (usize, usize) {
         let mut black = 0;
         let mut white = 0;
-        for point in board.points().iter() {
-            match point.color {
+        for (i, point) in board.points().iter().enumerate() {
+            let coord = Coord::from_index(i, board.size());
+            let owner = match point.color {
+                Empty => Empty,
+                color => match Score::stone_status(board, pass_alive, seki, &coord) {
+                    FinalStatus::Dead => color.opposite(),
+                    _                 => color
+                }
+            };
+            match owner {
                 Black => { black += 1; },
                 Empty => {},
                 White => { white += 1; },
@@ -145,9 +216,8 @@ impl Score {
 
         if neutral {
             territory_chain.set_color(Empty);
-        } else {
-            territory_chain.dedup();
         }
+        territory_chain.dedup();
 
         territory_chain
     }
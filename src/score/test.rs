@@ -22,10 +22,15 @@
 
 use board::Black;
 use board::Board;
+use board::Coord;
+use board::Empty;
 use board::Pass;
 use board::Play;
 use board::White;
 use ruleset::Minimal;
+use super::FinalStatus;
+use super::Influence;
+use super::Score;
 
 #[test]
 fn counting_simple_case() {
@@ -100,3 +105,82 @@ fn counting_with_neutral_points() {
     assert_eq!(White, score.color());
     assert_eq!("W+22.5", format!("{}", score));
 }
+
+#[test]
+fn stones_in_pass_alive_territory_count_for_the_opponent() {
+    let mut b = Board::new(5, 6.5, Minimal);
+
+    b.play(Play(White, 1, 1));
+    for col in range(1u8, 6) {
+        b.play(Play(Black, col, 2));
+    }
+    b.play(Play(Black, 3, 1));
+    b.play(Pass(White));
+    b.play(Pass(Black));
+
+    let score = Score::final_score(&b);
+    assert_eq!(25, score.black_stones());
+    assert_eq!(0, score.white_stones());
+    assert_eq!("B+18.5", format!("{}", score));
+}
+
+#[test]
+fn the_playout_score_counts_all_stones_as_alive() {
+    let mut b = Board::new(5, 6.5, Minimal);
+
+    b.play(Play(White, 1, 1));
+    for col in range(1u8, 6) {
+        b.play(Play(Black, col, 2));
+    }
+    b.play(Play(Black, 3, 1));
+
+    let score = b.score();
+    assert_eq!(23, score.black_stones());
+    assert_eq!(1, score.white_stones());
+}
+
+#[test]
+fn final_status_list_finds_dead_stones_and_seki() {
+    let mut b = Board::new(7, 6.5, Minimal);
+    for &(col, row) in [(1, 1), (1, 2), (1, 3), (6, 3), (6, 2), (6, 1)].iter() {
+        b.play(Play(Black, col, row));
+    }
+    for col in range(1u8, 7) {
+        b.play(Play(Black, col, 4));
+    }
+    for &(col, row) in [(2, 1), (2, 2), (2, 3), (3, 3), (4, 3), (5, 3), (5, 2), (5, 1)].iter() {
+        b.play(Play(White, col, row));
+    }
+    b.play(Play(Black, 3, 2));
+    b.play(Play(Black, 4, 2));
+
+    let seki = Score::final_status_list(&b, FinalStatus::Seki);
+    assert_eq!(2, seki.len());
+    assert!(seki.iter().any(|chain| chain.contains(&Coord::new(2, 1))));
+    assert!(seki.iter().any(|chain| chain.contains(&Coord::new(3, 2))));
+    assert_eq!(0, Score::final_status_list(&b, FinalStatus::Dead).len());
+    assert_eq!(1, Score::final_status_list(&b, FinalStatus::Alive).len());
+}
+
+#[test]
+fn influence_of_the_empty_board_is_the_komi() {
+    let b = Board::new(9, 6.5, Minimal);
+    let influence = Influence::new(&b);
+    assert_eq!(-6.5, influence.estimated_score(b.komi()));
+    assert_eq!("W+6.5", influence.estimate(b.komi()).as_slice());
+    assert!(influence.ownership().iter().all(|&o| o == 0.0));
+}
+
+#[test]
+fn influence_estimates_the_territory_behind_walls() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    for row in range(1u8, 10) {
+        b.play(Play(Black, 3, row));
+        b.play(Play(White, 7, row));
+    }
+    let influence = Influence::new(&b);
+    assert_eq!(Black, influence.owner(&Coord::new(1, 5)));
+    assert_eq!(Empty, influence.owner(&Coord::new(5, 5)));
+    assert_eq!(White, influence.owner(&Coord::new(9, 5)));
+    assert_eq!(-6.5, influence.estimated_score(b.komi()));
+}
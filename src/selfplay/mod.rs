@@ -0,0 +1,214 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Color;
+use board::Empty;
+use board::White;
+use engine::Engine;
+use game::Game;
+use ruleset::KgsChinese;
+use self::sprt::Decision;
+use self::sprt::Sprt;
+use self::sprt::elo;
+use sgf::Writer;
+
+use std::num::Float;
+
+pub mod sprt;
+mod test;
+
+// The z value for a 95% confidence interval.
+static Z: f32 = 1.96;
+
+// The results of a series of games from the point of view of the
+// first engine.
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub struct Results {
+    wins:   usize,
+    losses: usize,
+    draws:  usize
+}
+
+impl Results {
+    pub fn new() -> Results {
+        Results { wins: 0, losses: 0, draws: 0 }
+    }
+
+    // Records a game the first engine played with the given color.
+    pub fn add(&mut self, winner: Color, color: Color) {
+        if winner == Empty {
+            self.draws += 1;
+        } else if winner == color {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+
+    pub fn wins(&self) -> usize {
+        self.wins
+    }
+
+    pub fn losses(&self) -> usize {
+        self.losses
+    }
+
+    pub fn draws(&self) -> usize {
+        self.draws
+    }
+
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.draws
+    }
+
+    // Draws count as half a win.
+    pub fn win_rate(&self) -> f32 {
+        if self.games() == 0 {
+            0.0
+        } else {
+            (self.wins as f32 + self.draws as f32 / 2.0) / self.games() as f32
+        }
+    }
+
+    // The Wilson score interval of the win rate. Unlike the normal
+    // approximation it works for small numbers of games and win
+    // rates close to 0 or 1, too.
+    pub fn confidence_interval(&self) -> (f32, f32) {
+        if self.games() == 0 {
+            return (0.0, 1.0);
+        }
+        let n = self.games() as f32;
+        let p = self.win_rate();
+        let z2 = Z * Z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half = Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+        ((center - half).max(0.0), (center + half).min(1.0))
+    }
+
+    pub fn report(&self) -> String {
+        let (low, high) = self.confidence_interval();
+        format!("{} wins, {} losses, {} draws: {:.1}% (95% confidence interval {:.1}% - {:.1}%)",
+                self.wins, self.losses, self.draws, self.win_rate() * 100.0, low * 100.0, high * 100.0)
+    }
+}
+
+// Plays a game between the two engines until both pass or the game
+// gets too long. An engine that plays an illegal move loses. Returns
+// the game, the winner and the result in SGF notation.
+pub fn play_game<'a>(black: &Engine, white: &Engine, size: u8, komi: f32) -> (Game<'a>, Color, String) {
+    let mut game = Game::new(size, komi, KgsChinese);
+    let max_moves = size as usize * size as usize * 3;
+    while !game.is_over() && game.moves().len() < max_moves {
+        let color = game.next_player();
+        let engine = if color == Black { black } else { white };
+        let m = engine.gen_move(color, &game);
+        match game.play(m) {
+            Ok(g)  => game = g,
+            Err(_) => {
+                let result = if color == Black { "W+Forfeit" } else { "B+Forfeit" };
+                return (game, color.opposite(), String::from_str(result));
+            }
+        }
+    }
+    let winner = game.winner();
+    let result = format!("{}", game.score());
+    (game, winner, result)
+}
+
+// Plays games between two engines, alternating colors, on each of
+// the board sizes and prints the results.
+pub struct SelfPlay<'a> {
+    first:       Box<Engine + 'a>,
+    first_name:  String,
+    second:      Box<Engine + 'a>,
+    second_name: String
+}
+
+impl<'a> SelfPlay<'a> {
+    pub fn new(first: Box<Engine + 'a>, first_name: &str, second: Box<Engine + 'a>, second_name: &str) -> SelfPlay<'a> {
+        SelfPlay {
+            first:       first,
+            first_name:  String::from_str(first_name),
+            second:      second,
+            second_name: String::from_str(second_name)
+        }
+    }
+
+    // Plays the given number of games on each board size. Every game
+    // is written to the SGF directory if there is one.
+    pub fn run(&self, games: usize, sizes: &Vec<u8>, komi: f32, sgf_dir: Option<Path>) -> Results {
+        let mut total = Results::new();
+        for &size in sizes.iter() {
+            let mut results = Results::new();
+            for i in range(0, games) {
+                let color = if i % 2 == 0 { Black } else { White };
+                let (black, white, black_name, white_name) = if color == Black {
+                    (&*self.first, &*self.second, &self.first_name, &self.second_name)
+                } else {
+                    (&*self.second, &*self.first, &self.second_name, &self.first_name)
+                };
+                let (game, winner, result) = play_game(black, white, size, komi);
+                results.add(winner, color);
+                total.add(winner, color);
+                println!("{}x{} game {}: {} (B) vs {} (W): {}",
+                         size, size, i + 1, black_name, white_name, result);
+                match sgf_dir {
+                    Some(ref dir) => {
+                        let path = dir.join(format!("{}x{}-{:04}.sgf", size, size, i + 1));
+                        Writer::new(&game, black_name.as_slice(), white_name.as_slice())
+                            .with_result(result.as_slice())
+                            .save(path);
+                    },
+                    None => {}
+                }
+            }
+            println!("{} vs {} on {}x{}: {}", self.first_name, self.second_name, size, size, results.report());
+        }
+        println!("{} vs {}: {}", self.first_name, self.second_name, total.report());
+        total
+    }
+
+    // Plays games on one board size until the SPRT accepts or
+    // rejects, or the maximum number of games is reached. Prints the
+    // LLR and the Elo estimate after every game.
+    pub fn sprt(&self, sprt: &Sprt, max_games: usize, size: u8, komi: f32) -> (Results, Decision) {
+        let mut results = Results::new();
+        let (lower, upper) = sprt.bounds();
+        let mut decision = Decision::Continue;
+        for i in range(0, max_games) {
+            let color = if i % 2 == 0 { Black } else { White };
+            let (black, white) = if color == Black { (&*self.first, &*self.second) } else { (&*self.second, &*self.first) };
+            let (_, winner, _) = play_game(black, white, size, komi);
+            results.add(winner, color);
+            let (estimate, margin) = elo(&results);
+            println!("{} games: {}-{}-{}, LLR {:.2} ({:.2}, {:.2}), Elo {:.1} +/- {:.1}",
+                     results.games(), results.wins(), results.losses(), results.draws(),
+                     sprt.llr(&results), lower, upper, estimate, margin);
+            decision = sprt.decide(&results);
+            if decision != Decision::Continue {
+                break;
+            }
+        }
+        println!("{} vs {}: {:?} after {} games", self.first_name, self.second_name, decision, results.games());
+        (results, decision)
+    }
+}
@@ -0,0 +1,120 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use super::Results;
+
+use std::num::Float;
+
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub enum Decision {
+    // The engine is at least elo1 stronger.
+    Accept,
+    // The engine is at most elo0 stronger.
+    Reject,
+    Continue
+}
+
+// A sequential probability ratio test of the hypothesis that the
+// first engine is elo1 stronger than the second against the
+// hypothesis that it's only elo0 stronger. The log-likelihood ratio
+// uses the normal approximation of the game results, so draws are
+// handled correctly. The results are regularized with half a win and
+// half a loss, as otherwise a perfect score has no variance and
+// never leads to a decision.
+#[derive(Clone, Copy, Show)]
+pub struct Sprt {
+    alpha: f64,
+    beta:  f64,
+    elo0:  f64,
+    elo1:  f64
+}
+
+impl Sprt {
+    pub fn new(elo0: f64, elo1: f64, alpha: f64, beta: f64) -> Sprt {
+        Sprt { alpha: alpha, beta: beta, elo0: elo0, elo1: elo1 }
+    }
+
+    // The test stops once the LLR leaves these bounds.
+    pub fn bounds(&self) -> (f64, f64) {
+        ((self.beta / (1.0 - self.alpha)).ln(), ((1.0 - self.beta) / self.alpha).ln())
+    }
+
+    pub fn llr(&self, results: &Results) -> f64 {
+        if results.games() == 0 {
+            return 0.0;
+        }
+        let (wins, draws, losses) = (results.wins() as f64 + 0.5, results.draws() as f64, results.losses() as f64 + 0.5);
+        let games = wins + draws + losses;
+        let (mean, variance) = trinomial_moments(wins, draws, losses);
+        let s0 = expected_score(self.elo0);
+        let s1 = expected_score(self.elo1);
+        games * (s1 - s0) * (2.0 * mean - s0 - s1) / (2.0 * variance)
+    }
+
+    pub fn decide(&self, results: &Results) -> Decision {
+        let (lower, upper) = self.bounds();
+        let llr = self.llr(results);
+        if llr >= upper {
+            Decision::Accept
+        } else if llr <= lower {
+            Decision::Reject
+        } else {
+            Decision::Continue
+        }
+    }
+}
+
+// The Elo difference and the 95% error margin that the results
+// suggest. Without a single win or loss the difference is infinite.
+pub fn elo(results: &Results) -> (f64, f64) {
+    if results.games() == 0 {
+        return (0.0, Float::infinity());
+    }
+    let (mean, variance) = moments(results);
+    let margin = 1.96 * (variance / results.games() as f64).sqrt();
+    let low = score_to_elo((mean - margin).max(0.0));
+    let high = score_to_elo((mean + margin).min(1.0));
+    (score_to_elo(mean), (high - low) / 2.0)
+}
+
+fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+fn score_to_elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+// The mean and the variance of the score of a single game.
+fn moments(results: &Results) -> (f64, f64) {
+    let games = results.games() as f64;
+    if games == 0.0 {
+        return (0.0, 0.0);
+    }
+    trinomial_moments(results.wins() as f64, results.draws() as f64, results.losses() as f64)
+}
+
+fn trinomial_moments(wins: f64, draws: f64, losses: f64) -> (f64, f64) {
+    let games = wins + draws + losses;
+    let mean = (wins + draws / 2.0) / games;
+    let variance = (wins * (1.0 - mean).powi(2) + draws * (0.5 - mean).powi(2) + losses * mean.powi(2)) / games;
+    (mean, variance)
+}
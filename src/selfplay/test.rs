@@ -0,0 +1,169 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Empty;
+use board::White;
+use engine::RandomEngine;
+use super::Results;
+use super::SelfPlay;
+use super::play_game;
+use super::sprt::Decision;
+use super::sprt::Sprt;
+use super::sprt::elo;
+
+use std::num::Float;
+use std::io::TempDir;
+
+#[test]
+fn results_count_wins_from_the_first_engines_point_of_view() {
+    let mut r = Results::new();
+    r.add(Black, Black);
+    r.add(Black, White);
+    r.add(White, White);
+    r.add(Empty, Black);
+    assert_eq!(2, r.wins());
+    assert_eq!(1, r.losses());
+    assert_eq!(1, r.draws());
+    assert_eq!(4, r.games());
+}
+
+#[test]
+fn draws_count_as_half_a_win() {
+    let mut r = Results::new();
+    r.add(Black, Black);
+    r.add(Empty, Black);
+    assert_eq!(0.75, r.win_rate());
+}
+
+#[test]
+fn the_confidence_interval_contains_the_win_rate() {
+    let mut r = Results::new();
+    for _ in range(0us, 7) {
+        r.add(Black, Black);
+    }
+    for _ in range(0us, 3) {
+        r.add(White, Black);
+    }
+    let (low, high) = r.confidence_interval();
+    assert!(low < 0.7 && 0.7 < high);
+    assert!(low >= 0.0 && high <= 1.0);
+}
+
+#[test]
+fn the_confidence_interval_gets_narrower_with_more_games() {
+    let mut few = Results::new();
+    let mut many = Results::new();
+    for i in range(0us, 100) {
+        let winner = if i % 2 == 0 { Black } else { White };
+        if i < 10 {
+            few.add(winner, Black);
+        }
+        many.add(winner, Black);
+    }
+    let (few_low, few_high) = few.confidence_interval();
+    let (many_low, many_high) = many.confidence_interval();
+    assert!(many_high - many_low < few_high - few_low);
+}
+
+#[test]
+fn a_game_between_random_engines_finishes() {
+    let engine = RandomEngine::new();
+    let (game, winner, result) = play_game(&engine, &engine, 5, 6.5);
+    assert!(game.moves().len() <= 75);
+    assert!(winner != Empty);
+    assert_eq!(format!("{}", game.score()), result);
+}
+
+#[test]
+fn run_plays_the_games_on_all_sizes_and_writes_sgfs() {
+    let selfplay = SelfPlay::new(Box::new(RandomEngine::new()), "random", Box::new(RandomEngine::new()), "random");
+    let dir = TempDir::new("selfplay").unwrap();
+    let results = selfplay.run(2, &vec!(5, 7), 6.5, Some(dir.path().clone()));
+    assert_eq!(4, results.games());
+    assert!(dir.path().join("5x5-0002.sgf").exists());
+    assert!(dir.path().join("7x7-0001.sgf").exists());
+}
+
+fn results(wins: usize, losses: usize) -> Results {
+    let mut r = Results::new();
+    for _ in range(0, wins) {
+        r.add(Black, Black);
+    }
+    for _ in range(0, losses) {
+        r.add(White, Black);
+    }
+    r
+}
+
+#[test]
+fn the_sprt_bounds_depend_on_alpha_and_beta() {
+    let (lower, upper) = Sprt::new(0.0, 50.0, 0.05, 0.05).bounds();
+    assert!((lower + 2.944).abs() < 0.001);
+    assert!((upper - 2.944).abs() < 0.001);
+}
+
+#[test]
+fn the_llr_is_0_without_games() {
+    let sprt = Sprt::new(0.0, 50.0, 0.05, 0.05);
+    assert_eq!(0.0, sprt.llr(&Results::new()));
+    assert_eq!(Decision::Continue, sprt.decide(&Results::new()));
+}
+
+#[test]
+fn the_sprt_accepts_a_clear_improvement() {
+    let sprt = Sprt::new(0.0, 50.0, 0.05, 0.05);
+    assert!((sprt.llr(&results(60, 40)) - 1.901).abs() < 0.001);
+    assert_eq!(Decision::Continue, sprt.decide(&results(55, 45)));
+    assert_eq!(Decision::Accept, sprt.decide(&results(120, 80)));
+}
+
+#[test]
+fn the_sprt_rejects_a_regression() {
+    let sprt = Sprt::new(0.0, 50.0, 0.05, 0.05);
+    assert_eq!(Decision::Reject, sprt.decide(&results(40, 60)));
+}
+
+#[test]
+fn a_perfect_score_leads_to_a_decision() {
+    let sprt = Sprt::new(0.0, 50.0, 0.05, 0.05);
+    assert_eq!(Decision::Accept, sprt.decide(&results(30, 0)));
+    assert_eq!(Decision::Reject, sprt.decide(&results(0, 30)));
+}
+
+#[test]
+fn the_elo_estimate_follows_the_win_rate() {
+    let (estimate, margin) = elo(&results(75, 25));
+    assert!((estimate - 190.85).abs() < 0.01);
+    assert!(margin > 0.0);
+    assert_eq!(0.0, elo(&results(50, 50)).0);
+}
+
+#[test]
+fn the_sprt_stops_after_the_maximum_number_of_games() {
+    let selfplay = SelfPlay::new(Box::new(RandomEngine::new()), "random", Box::new(RandomEngine::new()), "random");
+    // Even 4 wins in a row aren't enough to leave the bounds.
+    let (results, decision) = selfplay.sprt(&Sprt::new(0.0, 50.0, 0.05, 0.05), 4, 5, 6.5);
+    assert_eq!(4, results.games());
+    assert_eq!(Decision::Continue, decision);
+}
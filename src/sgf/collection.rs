@@ -0,0 +1,56 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2014-2015 Urban Hafner                                     *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use super::Parser;
+
+use std::io::fs::readdir;
+use std::io::stdio::stderr;
+
+// The SGF files in the directory, sorted by name.
+pub fn sgf_files(directory: &Path) -> Vec<Path> {
+    let mut paths: Vec<Path> = readdir(directory)
+        .unwrap()
+        .into_iter()
+        .filter(|p| p.extension_str() == Some("sgf"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+// The parser for the file, if it can be read and its game replayed.
+// Otherwise the problem is printed to stderr, so that a broken file
+// only gets skipped instead of stopping a run over a whole collection.
+pub fn load(path: &Path) -> Option<Parser> {
+    let parser = match Parser::open(path) {
+        Ok(parser) => parser,
+        Err(e)     => {
+            let _ = stderr().write_line(format!("Skipping {}", e).as_slice());
+            return None;
+        }
+    };
+    match parser.game() {
+        Ok(_)  => Some(parser),
+        Err(e) => {
+            let _ = stderr().write_line(format!("Skipping {}: {:?}", path.display(), e).as_slice());
+            None
+        }
+    }
+}
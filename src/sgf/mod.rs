@@ -20,6 +20,9 @@
  ************************************************************************/
 
 pub use self::parser::Parser;
+pub use self::writer::Writer;
 
+pub mod collection;
 pub mod parser;
+pub mod writer;
 mod test;
@@ -26,6 +26,7 @@ use board::Black;
 use board::Color;
 use board::Empty;
 use board::IllegalMove;
+use board::Move;
 use board::White;
 use board::movement::Pass;
 use board::movement::Play;
@@ -63,9 +64,28 @@ impl<'a> Property<'a> {
     }
 
     fn is_move(&self) -> bool {
+        self.is_setup() || self.is_play()
+    }
+
+    fn is_setup(&self) -> bool {
+        match self.name {
+            "AB" | "AW" => true,
+            _           => false
+        }
+    }
+
+    fn is_play(&self) -> bool {
         match self.name {
-            "AB" | "AW" | "B" | "W" => true,
-            _                       => false
+            "B" | "W" => true,
+            _         => false
+        }
+    }
+
+    fn to_move(&self, size: u8) -> Move {
+        if self.is_pass() {
+            Pass(self.color())
+        } else {
+            Play(self.color(), self.col(), self.row(size))
         }
     }
 
@@ -79,11 +99,8 @@ impl<'a> Property<'a> {
 
     fn play<'b>(&'b self, game: Game<'b>) -> Result<Game<'b>, IllegalMove> {
         if self.is_move() {
-            if self.is_pass() {
-                game.play(Pass(self.color()))
-            } else {
-                game.play(Play(self.color(), self.col(), self.row(game.size())))
-            }
+            let m = self.to_move(game.size());
+            game.play(m)
         } else {
             Ok(game)
         }
@@ -95,9 +112,26 @@ impl Parser {
         Parser {sgf: sgf}
     }
 
+    // Panics if the file can't be loaded, see open.
     pub fn from_path(path: Path) -> Parser {
-        let contents = File::open(&path).read_to_string();
-        Parser::new(contents.unwrap())
+        match Parser::open(&path) {
+            Ok(parser) => parser,
+            Err(e)     => panic!("{}", e)
+        }
+    }
+
+    // Fails if the file can't be read or if the size, the komi or a
+    // move can't be parsed.
+    pub fn open(path: &Path) -> Result<Parser, String> {
+        let contents = match File::open(path).read_to_string() {
+            Ok(contents) => contents,
+            Err(e)       => return Err(format!("Couldn't read {}: {}", path.display(), e))
+        };
+        let parser = Parser::new(contents);
+        match parser.validate() {
+            Ok(_)  => Ok(parser),
+            Err(e) => Err(format!("Couldn't parse {}: {}", path.display(), e))
+        }
     }
 
     pub fn game(&self) -> Result<Game, IllegalMove> {
@@ -114,6 +148,72 @@ impl Parser {
         Ok(game)
     }
 
+    // The game with only the setup stones (e.g. the handicap stones)
+    // on the board.
+    pub fn initial_game(&self) -> Result<Game, IllegalMove> {
+        let mut game = Game::new(self.size(), self.komi(), Minimal);
+        let props = self.tokenize();
+        for prop in props.iter().filter(|p| p.is_setup()) {
+            match prop.play(game) {
+                Ok(g) => {
+                    game = g;
+                },
+                Err(e) => return Err(e)
+            }
+        }
+        Ok(game)
+    }
+
+    // The game as it was before the move with the given number (the
+    // first move has the number 1) was played.
+    pub fn game_before(&self, move_number: usize) -> Result<Game, IllegalMove> {
+        let mut game = try!(self.initial_game());
+        let played = if move_number > 0 { move_number - 1 } else { 0 };
+        for &m in self.moves().iter().take(played) {
+            game = try!(game.play(m));
+        }
+        Ok(game)
+    }
+
+    // The moves of the game without the setup stones.
+    pub fn moves(&self) -> Vec<Move> {
+        let size = self.size();
+        self.tokenize()
+            .iter()
+            .filter(|p| p.is_play())
+            .map(|p| p.to_move(size))
+            .collect()
+    }
+
+    // The winner according to the result property, Empty for a
+    // draw or if the result is unknown.
+    pub fn winner(&self) -> Color {
+        let props = self.tokenize();
+        match props.iter().find(|p| p.name == "RE") {
+            Some(p) if p.val.starts_with("B+") => Black,
+            Some(p) if p.val.starts_with("W+") => White,
+            _                                  => Empty
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let props = self.tokenize();
+        let size: Option<u8> = props.iter().find(|p| p.name == "SZ").and_then(|p| p.val.parse());
+        match size {
+            Some(size) if size > 0 && size <= 25 => {},
+            _                                    => return Err(String::from_str("invalid board size"))
+        }
+        let komi: Option<f32> = props.iter().find(|p| p.name == "KM").and_then(|p| p.val.parse());
+        if komi.is_none() {
+            return Err(String::from_str("invalid komi"));
+        }
+        let vertex = regex!(r"^([a-z]{2})?$");
+        match props.iter().find(|p| p.is_move() && !vertex.is_match(p.val)) {
+            Some(p) => Err(format!("invalid move {}[{}]", p.name, p.val)),
+            None    => Ok(())
+        }
+    }
+
     fn size(&self) -> u8 {
         let props = self.tokenize();
         let prop = props.iter().find(|p| p.name == "SZ").unwrap();
@@ -0,0 +1,45 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2014-2015 Urban Hafner                                     *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use sgf::collection::load;
+use sgf::collection::sgf_files;
+
+#[test]
+fn sgf_files_only_returns_sgf_files_in_order() {
+    let paths = sgf_files(&Path::new("fixtures/sgf"));
+    assert!(paths.len() > 0);
+    assert!(paths.iter().all(|p| p.extension_str() == Some("sgf")));
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(sorted, paths);
+}
+
+#[test]
+fn load_returns_the_parser_of_a_valid_file() {
+    assert!(load(&Path::new("fixtures/sgf/twomoves.sgf")).is_some());
+}
+
+#[test]
+fn load_skips_a_missing_file() {
+    assert!(load(&Path::new("fixtures/sgf/missing.sgf")).is_none());
+}
@@ -19,4 +19,6 @@
  *                                                                      *
  ************************************************************************/
 
+mod collection;
 mod parser;
+mod writer;
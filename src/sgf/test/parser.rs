@@ -22,6 +22,8 @@
 #![cfg(test)]
 
 use board::Black;
+use board::Empty;
+use board::Play;
 use board::White;
 use sgf::parser::Parser;
 
@@ -66,3 +68,57 @@ fn finished_game() {
     let game   = parser.game().unwrap();
     assert!(game.is_over());
 }
+
+#[test]
+fn moves_returns_the_moves_without_the_setup_stones() {
+    let parser = Parser::from_path(sgf("twomoves"));
+    assert_eq!(parser.moves(), vec!(Play(Black, 4, 15), Play(White, 16, 7)));
+}
+
+#[test]
+fn moves_doesnt_contain_handicap_stones() {
+    let parser = Parser::from_path(sgf("handicap"));
+    assert_eq!(parser.moves(), vec!());
+}
+
+#[test]
+fn initial_game_only_contains_the_setup_stones() {
+    let parser = Parser::from_path(sgf("handicap"));
+    let game   = parser.initial_game().unwrap();
+    assert_eq!(game.get(4,4), Black);
+    let parser = Parser::from_path(sgf("twomoves"));
+    let game   = parser.initial_game().unwrap();
+    assert_eq!(game.get(4, 15), Empty);
+}
+
+#[test]
+fn game_before_stops_before_the_given_move() {
+    let parser = Parser::from_path(sgf("twomoves"));
+    let game   = parser.game_before(2).unwrap();
+    assert_eq!(game.get(4, 15), Black);
+    assert_eq!(game.get(16, 7), Empty);
+    let game   = parser.game_before(1).unwrap();
+    assert_eq!(game.get(4, 15), Empty);
+}
+
+#[test]
+fn winner_reads_the_result() {
+    let parser = Parser::from_path(sgf("opening"));
+    assert_eq!(parser.winner(), Black);
+}
+
+#[test]
+fn winner_is_empty_without_a_result() {
+    let parser = Parser::from_path(sgf("twomoves"));
+    assert_eq!(parser.winner(), Empty);
+}
+
+#[test]
+fn open_fails_for_a_missing_file() {
+    assert!(Parser::open(&sgf("missing")).is_err());
+}
+
+#[test]
+fn open_fails_for_a_malformed_file() {
+    assert!(Parser::open(&Path::new("fixtures/sgf-invalid/malformed.sgf")).is_err());
+}
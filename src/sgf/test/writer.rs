@@ -0,0 +1,61 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Pass;
+use board::Play;
+use board::White;
+use game::Game;
+use ruleset::KgsChinese;
+use sgf::Parser;
+use sgf::Writer;
+
+#[test]
+fn the_written_moves_can_be_read_back() {
+    let g = Game::new(9, 6.5, KgsChinese);
+    let g = g.play(Play(Black, 3, 3)).unwrap();
+    let g = g.play(Play(White, 7, 8)).unwrap();
+    let g = g.play(Pass(Black)).unwrap();
+    let sgf = Writer::new(&g, "black", "white").to_sgf();
+    let parser = Parser::new(sgf);
+    assert_eq!(g.moves(), &parser.moves());
+}
+
+#[test]
+fn the_header_contains_the_game_info() {
+    let g = Game::new(9, 6.5, KgsChinese);
+    let g = g.play(Play(Black, 3, 3)).unwrap();
+    let sgf = Writer::new(&g, "mc", "random").to_sgf();
+    assert!(sgf.as_slice().contains("SZ[9]"));
+    assert!(sgf.as_slice().contains("KM[6.5]"));
+    assert!(sgf.as_slice().contains("PB[mc]"));
+    assert!(sgf.as_slice().contains("PW[random]"));
+    assert!(sgf.as_slice().contains("RE[B+"));
+}
+
+#[test]
+fn the_result_can_be_overridden() {
+    let g = Game::new(9, 6.5, KgsChinese);
+    let sgf = Writer::new(&g, "mc", "random").with_result("W+Forfeit").to_sgf();
+    assert!(sgf.as_slice().contains("RE[W+Forfeit]"));
+}
@@ -0,0 +1,83 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Move;
+use board::Pass;
+use board::Play;
+use game::Game;
+use version;
+
+use std::io::fs::File;
+
+// Writes a finished game as SGF, including its result.
+pub struct Writer<'a> {
+    black:  String,
+    game:   &'a Game<'a>,
+    result: String,
+    white:  String
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(game: &'a Game<'a>, black: &str, white: &str) -> Writer<'a> {
+        Writer {
+            black:  String::from_str(black),
+            game:   game,
+            result: format!("{}", game.score()),
+            white:  String::from_str(white)
+        }
+    }
+
+    // Overrides the result, which is the score of the game by
+    // default. E.g. "W+Resign".
+    pub fn with_result(self, result: &str) -> Writer<'a> {
+        Writer { result: String::from_str(result), ..self }
+    }
+
+    pub fn to_sgf(&self) -> String {
+        let mut sgf = format!(
+            "(;GM[1]FF[4]CA[UTF-8]AP[Iomrascálaí:{}]SZ[{}]KM[{}]RU[Chinese]PB[{}]PW[{}]RE[{}]\n",
+            version::version(), self.game.size(), self.game.komi(), self.black, self.white, self.result);
+        for m in self.game.moves().iter() {
+            sgf.push_str(format!(";{}\n", self.property(m)).as_slice());
+        }
+        sgf.push_str(")\n");
+        sgf
+    }
+
+    pub fn save(&self, path: Path) {
+        let mut file = File::create(&path).unwrap();
+        file.write_str(self.to_sgf().as_slice()).unwrap();
+    }
+
+    // SGF counts the rows from top to bottom, starting with a.
+    fn property(&self, m: &Move) -> String {
+        let name = if *m.color() == Black { "B" } else { "W" };
+        match *m {
+            Pass(_)           => format!("{}[]", name),
+            Play(_, col, row) => {
+                let c = ('a' as u8 + col - 1) as char;
+                let r = ('a' as u8 + self.game.size() - row) as char;
+                format!("{}[{}{}]", name, c, r)
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Board;
+use board::Chain;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::White;
+
+mod test;
+
+// A maximal connected set of intersections that don't contain a
+// stone of the color we're looking at.
+struct Region {
+    chains: Vec<usize>,
+    coords: Vec<Coord>,
+    empty:  Vec<Coord>,
+}
+
+impl Region {
+
+    // A region is vital to a chain if all its empty intersections
+    // are liberties of the chain.
+    fn is_vital_to(&self, chain: &Chain) -> bool {
+        self.empty.len() > 0 && self.empty.iter().all(|c| chain.liberties().contains(c))
+    }
+}
+
+// Benson's algorithm for unconditional life: The chains found can't
+// be captured even if their owner passes every move. The territory
+// found consists of the regions enclosed by these chains where the
+// opponent can't make an eye.
+pub struct PassAlive {
+    chains: Vec<usize>,
+    owner:  Vec<Color>,
+    size:   u8,
+}
+
+impl PassAlive {
+    pub fn new(board: &Board) -> PassAlive {
+        let size = board.size();
+        let mut pass_alive = PassAlive {
+            chains: Vec::new(),
+            owner:  range(0, size as usize * size as usize).map(|_| Empty).collect(),
+            size:   size,
+        };
+        pass_alive.find(board, Black);
+        pass_alive.find(board, White);
+        pass_alive
+    }
+
+    // The ids of the pass-alive chains of both colors.
+    pub fn chains(&self) -> &Vec<usize> {
+        &self.chains
+    }
+
+    pub fn is_alive(&self, chain_id: usize) -> bool {
+        self.chains.contains(&chain_id)
+    }
+
+    // The color that owns the intersection for certain. That's the
+    // color of the pass-alive stones and the color of the owner of
+    // the pass-alive territory (including the dead stones in it).
+    pub fn owner(&self, c: &Coord) -> Color {
+        self.owner[c.to_index(self.size)]
+    }
+
+    pub fn is_settled(&self, c: &Coord) -> bool {
+        self.owner(c) != Empty
+    }
+
+    // All intersections that color owns for certain, apart from its
+    // own pass-alive stones.
+    pub fn territory(&self, board: &Board, color: Color) -> Vec<Coord> {
+        Coord::for_board_size(self.size)
+            .into_iter()
+            .filter(|c| self.owner(c) == color && board.color(c) != color)
+            .collect()
+    }
+
+    fn find(&mut self, board: &Board, color: Color) {
+        let mut alive: Vec<usize> = board.chains()
+            .iter()
+            .filter(|c| c.color() == color)
+            .map(|c| c.id())
+            .collect();
+        let mut regions = PassAlive::regions(board, color);
+        loop {
+            let before = (alive.len(), regions.len());
+            // Remove all chains with less than two vital regions
+            alive = alive.into_iter()
+                .filter(|&id| regions.iter().filter(|r| r.is_vital_to(&board.chains()[id])).count() >= 2)
+                .collect();
+            // Remove all regions that touch a chain that was removed
+            regions = regions.into_iter()
+                .filter(|r| r.chains.iter().all(|id| alive.contains(id)))
+                .collect();
+            if before == (alive.len(), regions.len()) {
+                break;
+            }
+        }
+        for &id in alive.iter() {
+            for c in board.chains()[id].coords().iter() {
+                self.owner[c.to_index(self.size)] = color;
+            }
+        }
+        for region in regions.iter() {
+            let is_territory = region.empty.iter().all(|&e| {
+                board.neighbours(e).iter().any(|c| board.color(c) == color && alive.contains(&board.chain_id(c)))
+            });
+            if is_territory {
+                for c in region.coords.iter() {
+                    self.owner[c.to_index(self.size)] = color;
+                }
+            }
+        }
+        self.chains.push_all(alive.as_slice());
+    }
+
+    fn regions(board: &Board, color: Color) -> Vec<Region> {
+        let mut visited: Vec<bool> = board.points().iter().map(|_| false).collect();
+        let mut regions = Vec::new();
+        for start in Coord::for_board_size(board.size()).into_iter() {
+            if board.color(&start) == color || visited[start.to_index(board.size())] {
+                continue;
+            }
+            let mut region = Region { chains: Vec::new(), coords: Vec::new(), empty: Vec::new() };
+            let mut to_visit = vec!(start);
+            visited[start.to_index(board.size())] = true;
+            while to_visit.len() > 0 {
+                let coord = to_visit.pop().unwrap();
+                region.coords.push(coord);
+                if board.color(&coord) == Empty {
+                    region.empty.push(coord);
+                }
+                for &n in board.neighbours(coord).iter() {
+                    if board.color(&n) == color {
+                        region.chains.push(board.chain_id(&n));
+                    } else if !visited[n.to_index(board.size())] {
+                        visited[n.to_index(board.size())] = true;
+                        to_visit.push(n);
+                    }
+                }
+            }
+            region.chains.sort();
+            region.chains.dedup();
+            regions.push(region);
+        }
+        regions
+    }
+}
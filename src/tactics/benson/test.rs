@@ -0,0 +1,74 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Empty;
+use board::Play;
+use board::White;
+use ruleset::Minimal;
+use super::PassAlive;
+
+#[test]
+fn a_chain_with_three_eyes_is_pass_alive() {
+    let mut b = Board::new(5, 6.5, Minimal);
+    for col in range(1u8, 6) {
+        b.play(Play(White, col, 3));
+    }
+    for &(col, row) in [(2, 1), (4, 1), (1, 2), (2, 2), (3, 2), (4, 2), (5, 2)].iter() {
+        b.play(Play(Black, col, row));
+    }
+    let pass_alive = PassAlive::new(&b);
+    let black_id = b.chain_id(&Coord::new(2, 2));
+    let white_id = b.chain_id(&Coord::new(1, 3));
+    assert!(pass_alive.is_alive(black_id));
+    assert!(!pass_alive.is_alive(white_id));
+    assert_eq!(vec!(Coord::new(1, 1), Coord::new(3, 1), Coord::new(5, 1)), pass_alive.territory(&b, Black));
+    assert_eq!(Empty, pass_alive.owner(&Coord::new(3, 4)));
+}
+
+#[test]
+fn a_chain_with_one_eye_is_not_pass_alive() {
+    let mut b = Board::new(5, 6.5, Minimal);
+    for &(col, row) in [(2, 1), (1, 2), (2, 2)].iter() {
+        b.play(Play(Black, col, row));
+    }
+    let pass_alive = PassAlive::new(&b);
+    assert_eq!(0, pass_alive.chains().len());
+    assert!(!pass_alive.is_settled(&Coord::new(1, 1)));
+}
+
+#[test]
+fn stones_inside_pass_alive_territory_are_owned_by_the_opponent() {
+    let mut b = Board::new(5, 6.5, Minimal);
+    b.play(Play(White, 1, 1));
+    for col in range(1u8, 6) {
+        b.play(Play(Black, col, 2));
+    }
+    b.play(Play(Black, 3, 1));
+    let pass_alive = PassAlive::new(&b);
+    assert_eq!(Black, pass_alive.owner(&Coord::new(1, 1)));
+    assert_eq!(Black, pass_alive.owner(&Coord::new(2, 1)));
+    assert_eq!(vec!(Coord::new(1, 1), Coord::new(2, 1), Coord::new(4, 1), Coord::new(5, 1)), pass_alive.territory(&b, Black));
+}
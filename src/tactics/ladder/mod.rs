@@ -0,0 +1,200 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+pub use self::Ladder::Broken;
+pub use self::Ladder::Captured;
+pub use self::Ladder::Escaped;
+pub use self::Ladder::NoLadder;
+use board::Board;
+use board::Chain;
+use board::Coord;
+use board::Move;
+use board::Play;
+
+mod test;
+
+// A ladder along the diagonal of a 19x19 board is roughly 70 moves
+// long. Anything deeper than this is a bug or something that isn't a
+// ladder.
+static MAX_DEPTH: usize = 200;
+
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub enum Ladder {
+    // The chain can be captured by reading out the ladder
+    Captured,
+    // The chain escapes, e.g. by capturing one of the attacking stones
+    Escaped,
+    // The chain escapes because it connects to the ladder breaker at
+    // the given coord
+    Broken(Coord),
+    // The chain isn't in atari (or doesn't have two liberties with
+    // the attacker to move)
+    NoLadder
+}
+
+// Reads out the ladder of the chain at `coord` starting with the
+// player who is next to move on the board. A chain in atari with
+// the defender to move tries to run, a chain with two liberties
+// with the attacker to move gets chased.
+pub fn read(board: &Board, coord: Coord) -> Ladder {
+    let chain = match board.get_chain(coord) {
+        Some(chain) => chain,
+        None        => return NoLadder
+    };
+    let defender_to_move = board.next_player() == chain.color();
+    match chain.liberties().len() {
+        1 if defender_to_move  => defend(board, coord, 0),
+        1                      => capture(board, chain),
+        2 if !defender_to_move => attack(board, coord, 0),
+        _                      => NoLadder
+    }
+}
+
+// Checks if a move is a waste of time as far as ladders are
+// concerned: Either it extends a chain in atari that still gets
+// captured in a ladder or it starts chasing a chain that escapes.
+pub fn is_wasted_move(board: &Board, m: &Move) -> bool {
+    if m.is_pass() {
+        return false;
+    }
+    let coord = m.coord();
+    let color = *m.color();
+    let extends = board.neighbours(coord)
+        .iter()
+        .filter(|&c| board.color(c) == color)
+        .any(|&c| board.get_chain(c).unwrap().liberties().len() == 1);
+    let chased: Vec<Coord> = board.neighbours(coord)
+        .iter()
+        .filter(|&c| board.color(c) == color.opposite())
+        .filter(|&c| board.get_chain(*c).unwrap().liberties().len() == 2)
+        .cloned()
+        .collect();
+    if !extends && chased.len() == 0 {
+        return false;
+    }
+    let mut after = board.clone();
+    if after.play(*m).is_err() || after.adv_stones_removed().len() > 0 {
+        return false;
+    }
+    if extends {
+        read(&after, coord) == Captured
+    } else {
+        chased.iter().all(|&c| match read(&after, c) {
+            Escaped | Broken(_) => true,
+            _                   => false
+        })
+    }
+}
+
+fn capture(board: &Board, chain: &Chain) -> Ladder {
+    let lib = *chain.liberties().iter().next().unwrap();
+    let mut b = board.clone();
+    match b.play(Play(chain.color().opposite(), lib.col, lib.row)) {
+        Ok(_)  => Captured,
+        // Most likely a ko
+        Err(_) => Escaped
+    }
+}
+
+// The chain at coord is in atari and the defender is to move. It
+// can either capture an adjacent attacking chain that is in atari
+// itself or extend on its last liberty.
+fn defend(board: &Board, coord: Coord, depth: usize) -> Ladder {
+    if depth > MAX_DEPTH {
+        return Escaped;
+    }
+    let chain = board.get_chain(coord).unwrap();
+    let color = chain.color();
+    let mut candidates = capturing_coords(board, chain);
+    candidates.push(*chain.liberties().iter().next().unwrap());
+    for &candidate in candidates.iter() {
+        let mut b = board.clone();
+        if b.play(Play(color, candidate.col, candidate.row)).is_err() {
+            continue;
+        }
+        let result = match b.get_chain(coord) {
+            None            => Captured,
+            Some(new_chain) => match new_chain.liberties().len() {
+                0 | 1 => Captured,
+                2     => attack(&b, coord, depth + 1),
+                _     => escape(chain, new_chain, candidate)
+            }
+        };
+        if result != Captured {
+            return result;
+        }
+    }
+    Captured
+}
+
+// The chain at coord has two liberties and the attacker is to
+// move. The attacker tries putting it into atari from either side.
+fn attack(board: &Board, coord: Coord, depth: usize) -> Ladder {
+    if depth > MAX_DEPTH {
+        return Escaped;
+    }
+    let chain = board.get_chain(coord).unwrap();
+    let attacker = chain.color().opposite();
+    let mut result = Escaped;
+    for &lib in chain.liberties().iter() {
+        let mut b = board.clone();
+        if b.play(Play(attacker, lib.col, lib.row)).is_err() {
+            continue;
+        }
+        let outcome = match b.get_chain(coord) {
+            Some(c) if c.liberties().len() == 1 => defend(&b, coord, depth + 1),
+            _                                   => Escaped
+        };
+        match outcome {
+            Captured  => return Captured,
+            Broken(c) => result = Broken(c),
+            _         => {}
+        }
+    }
+    result
+}
+
+// Coords of the last liberties of the attacking chains next to the
+// defending chain that are in atari.
+fn capturing_coords(board: &Board, chain: &Chain) -> Vec<Coord> {
+    let attacker = chain.color().opposite();
+    let mut coords: Vec<Coord> = chain.coords()
+        .iter()
+        .flat_map(|&c| board.neighbours(c).iter())
+        .filter(|&c| board.color(c) == attacker)
+        .map(|&c| board.get_chain(c).unwrap())
+        .filter(|c| c.liberties().len() == 1)
+        .map(|c| *c.liberties().iter().next().unwrap())
+        .collect();
+    coords.sort();
+    coords.dedup();
+    coords
+}
+
+// The chain got out of atari with at least three liberties. If it
+// did so by connecting to a friendly stone then that stone is the
+// ladder breaker.
+fn escape(old: &Chain, new: &Chain, played: Coord) -> Ladder {
+    match new.coords().iter().find(|&&c| c != played && !old.coords().contains(&c)) {
+        Some(&breaker) => Broken(breaker),
+        None           => Escaped
+    }
+}
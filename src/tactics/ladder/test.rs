@@ -0,0 +1,119 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Play;
+use board::White;
+use ruleset::Minimal;
+use super::Broken;
+use super::Captured;
+use super::NoLadder;
+use super::is_wasted_move;
+use super::read;
+
+// A black stone in atari that runs towards the lower right corner
+// of an otherwise empty board.
+fn ladder_board<'a>() -> Board<'a> {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 5, 5));
+    b.play(Play(White, 4, 5));
+    b.play(Play(White, 5, 6));
+    b.play(Play(White, 4, 4));
+    b.play(Play(White, 6, 5));
+    b
+}
+
+#[test]
+fn a_ladder_on_an_empty_board_works() {
+    let b = ladder_board();
+    assert_eq!(Captured, read(&b, Coord::new(5, 5)));
+}
+
+#[test]
+fn a_ladder_breaker_saves_the_chain() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 7, 2));
+    b.play(Play(Black, 5, 5));
+    b.play(Play(White, 4, 5));
+    b.play(Play(White, 5, 6));
+    b.play(Play(White, 4, 4));
+    b.play(Play(White, 6, 5));
+    assert_eq!(Broken(Coord::new(7, 2)), read(&b, Coord::new(5, 5)));
+}
+
+#[test]
+fn the_attacker_chases_a_chain_with_two_libs() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(White, 4, 5));
+    b.play(Play(White, 5, 6));
+    b.play(Play(White, 4, 4));
+    b.play(Play(Black, 5, 5));
+    assert_eq!(Captured, read(&b, Coord::new(5, 5)));
+}
+
+#[test]
+fn a_chain_with_two_libs_and_the_defender_to_move_is_no_ladder() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 5, 5));
+    b.play(Play(White, 4, 5));
+    b.play(Play(White, 5, 6));
+    b.play(Play(White, 4, 4));
+    assert_eq!(NoLadder, read(&b, Coord::new(5, 5)));
+}
+
+#[test]
+fn an_empty_intersection_is_no_ladder() {
+    let b = ladder_board();
+    assert_eq!(NoLadder, read(&b, Coord::new(1, 1)));
+}
+
+#[test]
+fn running_out_a_dead_ladder_is_a_wasted_move() {
+    let b = ladder_board();
+    assert!(is_wasted_move(&b, &Play(Black, 5, 4)));
+}
+
+#[test]
+fn running_with_a_ladder_breaker_is_not_a_wasted_move() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 7, 2));
+    b.play(Play(Black, 5, 5));
+    b.play(Play(White, 4, 5));
+    b.play(Play(White, 5, 6));
+    b.play(Play(White, 4, 4));
+    b.play(Play(White, 6, 5));
+    assert!(!is_wasted_move(&b, &Play(Black, 5, 4)));
+}
+
+#[test]
+fn chasing_a_broken_ladder_is_a_wasted_move() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 4, 5));
+    b.play(Play(Black, 5, 6));
+    b.play(Play(Black, 4, 4));
+    b.play(Play(White, 7, 2));
+    b.play(Play(White, 5, 5));
+    assert!(is_wasted_move(&b, &Play(Black, 6, 5)));
+}
@@ -0,0 +1,103 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Chain;
+use board::Color;
+use board::Coord;
+use board::Play;
+
+mod test;
+
+// Finds the ids of all chains that are in seki. A chain is in seki
+// if it has at most one eye, all its other liberties are shared with
+// opposing chains, and approaching any of the shared liberties is a
+// self-atari for both players. Additionally all the opposing chains
+// it shares liberties with have to be in seki, too.
+pub fn seki_chains(board: &Board) -> Vec<usize> {
+    let mut candidates: Vec<(usize, Vec<usize>)> = board.chains()
+        .iter()
+        .filter_map(|chain| partners_if_candidate(board, chain).map(|p| (chain.id(), p)))
+        .collect();
+    loop {
+        let ids: Vec<usize> = candidates.iter().map(|&(id, _)| id).collect();
+        let before = candidates.len();
+        candidates = candidates.into_iter()
+            .filter(|&(_, ref partners)| partners.iter().all(|id| ids.contains(id)))
+            .collect();
+        if before == candidates.len() {
+            break;
+        }
+    }
+    candidates.into_iter().map(|(id, _)| id).collect()
+}
+
+// Returns the ids of the opposing chains that share liberties with
+// the chain if it could be in seki.
+fn partners_if_candidate(board: &Board, chain: &Chain) -> Option<Vec<usize>> {
+    let color = chain.color();
+    let eyes: Vec<&Coord> = chain.liberties()
+        .iter()
+        .filter(|&&lib| board.neighbours(lib).iter().all(|c| board.color(c) == color))
+        .collect();
+    let shared: Vec<Coord> = chain.liberties()
+        .iter()
+        .filter(|lib| !eyes.contains(lib))
+        .cloned()
+        .collect();
+    if eyes.len() > 1 || shared.len() == 0 {
+        return None;
+    }
+    let mut partners = Vec::new();
+    for &lib in shared.iter() {
+        let opponents: Vec<usize> = board.neighbours(lib)
+            .iter()
+            .filter(|c| board.color(*c) == color.opposite())
+            .map(|c| board.chain_id(c))
+            .collect();
+        if opponents.len() == 0 || !is_self_atari(board, color, lib) || !is_self_atari(board, color.opposite(), lib) {
+            return None;
+        }
+        partners.push_all(opponents.as_slice());
+    }
+    partners.sort();
+    partners.dedup();
+    Some(partners)
+}
+
+// Playing on the coord doesn't capture anything and leaves the chain
+// of the player with at most one liberty (or isn't even legal).
+fn is_self_atari(board: &Board, color: Color, coord: Coord) -> bool {
+    let mut b = board.clone();
+    if b.next_player() != color {
+        b.tenuki();
+    }
+    if b.play(Play(color, coord.col, coord.row)).is_err() {
+        return true;
+    }
+    if b.adv_stones_removed().len() > 0 {
+        return false;
+    }
+    match b.get_chain(coord) {
+        Some(chain) => chain.liberties().len() <= 1,
+        None        => true
+    }
+}
@@ -0,0 +1,71 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Play;
+use board::White;
+use ruleset::Minimal;
+use super::seki_chains;
+
+// A white ring inside of a black wall that encloses two black
+// stones. Both inner chains share the two liberties on the edge.
+fn seki_board<'a>(inner: &[(u8, u8)]) -> Board<'a> {
+    let mut b = Board::new(7, 6.5, Minimal);
+    for &(col, row) in [(1, 1), (1, 2), (1, 3), (6, 3), (6, 2), (6, 1)].iter() {
+        b.play(Play(Black, col, row));
+    }
+    for col in range(1u8, 7) {
+        b.play(Play(Black, col, 4));
+    }
+    for &(col, row) in [(2, 1), (2, 2), (2, 3), (3, 3), (4, 3), (5, 3), (5, 2), (5, 1)].iter() {
+        b.play(Play(White, col, row));
+    }
+    for &(col, row) in inner.iter() {
+        b.play(Play(Black, col, row));
+    }
+    b
+}
+
+#[test]
+fn chains_sharing_two_liberties_without_eyes_are_in_seki() {
+    let b = seki_board(&[(3, 2), (4, 2)]);
+    let mut seki = seki_chains(&b);
+    seki.sort();
+    let mut expected = vec!(b.chain_id(&Coord::new(2, 1)), b.chain_id(&Coord::new(3, 2)));
+    expected.sort();
+    assert_eq!(expected, seki);
+}
+
+#[test]
+fn a_chain_that_can_be_captured_is_not_in_seki() {
+    let b = seki_board(&[(3, 2)]);
+    assert_eq!(0, seki_chains(&b).len());
+}
+
+#[test]
+fn the_empty_board_has_no_seki() {
+    let b = Board::new(9, 6.5, Minimal);
+    assert_eq!(0, seki_chains(&b).len());
+}
@@ -0,0 +1,299 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Board;
+use board::Chain;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Move;
+use board::Pass;
+use board::Play;
+
+use std::collections::HashSet;
+
+mod test;
+
+// Races with more liberties than this are left alone when checking
+// moves during the search, as reading them out takes too long.
+static MAX_LIBERTIES: usize = 3;
+
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub struct Liberties {
+    outside: usize,
+    shared:  usize,
+    eyes:    usize
+}
+
+impl Liberties {
+    // Liberties only this chain has that aren't eyes
+    pub fn outside(&self) -> usize {
+        self.outside
+    }
+
+    // Liberties this chain shares with the opposing chain
+    pub fn shared(&self) -> usize {
+        self.shared
+    }
+
+    // Liberties completely surrounded by stones of this chain's color
+    pub fn eyes(&self) -> usize {
+        self.eyes
+    }
+
+    // The liberties the opponent has to fill before it can capture
+    // the chain, not counting the shared ones.
+    fn own(&self) -> usize {
+        self.outside + self.eyes
+    }
+}
+
+// A capturing race between two adjacent chains of opposite
+// colors. The chains are identified by one of their stones.
+pub struct Semeai<'a> {
+    board:  Board<'a>,
+    first:  Coord,
+    second: Coord
+}
+
+impl<'a> Semeai<'a> {
+    // Returns None if the chains don't have opposite colors or
+    // don't touch each other.
+    pub fn new(board: &Board<'a>, first: &Chain, second: &Chain) -> Option<Semeai<'a>> {
+        if first.color() == Empty || first.color() != second.color().opposite() {
+            return None;
+        }
+        let adjacent = first.coords()
+            .iter()
+            .flat_map(|&c| board.neighbours(c).iter())
+            .any(|c| second.coords().contains(c));
+        if !adjacent {
+            return None;
+        }
+        Some(Semeai {
+            board:  board.clone(),
+            first:  first.coords()[0],
+            second: second.coords()[0]
+        })
+    }
+
+    // The liberties of the chain that has a stone at coord.
+    pub fn liberties(&self, coord: Coord) -> Liberties {
+        let (own, other) = if self.board.chain_id(&coord) == self.board.chain_id(&self.first) {
+            (self.first, self.second)
+        } else {
+            (self.second, self.first)
+        };
+        count_liberties(&self.board, own, other)
+    }
+
+    // The color that wins the race if the given color moves
+    // first. Empty means that the race ends in seki.
+    pub fn winner(&self, to_move: Color) -> Color {
+        match self.count(to_move) {
+            Some(color) => color,
+            None        => self.read(to_move)
+        }
+    }
+
+    // Decides the race by counting liberties, which works if there
+    // are no shared liberties or only one of the chains has an eye.
+    // The shared liberties count for the chain with the eye then, as
+    // the other chain can't approach them without self-atari.
+    fn count(&self, to_move: Color) -> Option<Color> {
+        let first = self.liberties(self.first);
+        let second = self.liberties(self.second);
+        let (first_libs, second_libs) = if first.shared() == 0 {
+            (first.own(), second.own())
+        } else if first.eyes() > 0 && second.eyes() == 0 {
+            (first.own() + first.shared(), second.own())
+        } else if second.eyes() > 0 && first.eyes() == 0 {
+            (first.own(), second.own() + second.shared())
+        } else {
+            return None;
+        };
+        let first_color = self.board.color(&self.first);
+        // The player to move wins if it has at least as many
+        // liberties as the opponent.
+        let first_wins = if to_move == first_color {
+            first_libs >= second_libs
+        } else {
+            first_libs > second_libs
+        };
+        Some(if first_wins { first_color } else { first_color.opposite() })
+    }
+
+    // Reads out the race by playing on the liberties of both chains.
+    fn read(&self, to_move: Color) -> Color {
+        let mut board = self.board.clone();
+        if board.next_player() != to_move {
+            board.tenuki();
+        }
+        let libs = self.all_liberties(&board).len();
+        let first_color = self.board.color(&self.first);
+        let value = self.search(&board, 0, 0, libs * 2 + 4, -1, 1);
+        if value > 0 {
+            first_color
+        } else if value < 0 {
+            first_color.opposite()
+        } else {
+            Empty
+        }
+    }
+
+    // Alpha-beta search from the point of view of the first chain:
+    // 1 means it captures the second chain, -1 that it gets
+    // captured, and 0 that both chains survive.
+    fn search(&self, board: &Board, depth: usize, passes: usize, max_depth: usize, alpha: i8, beta: i8) -> i8 {
+        let first_color = self.board.color(&self.first);
+        if board.color(&self.first) != first_color {
+            return -1;
+        }
+        if board.color(&self.second) != first_color.opposite() {
+            return 1;
+        }
+        if passes >= 2 || depth >= max_depth {
+            return 0;
+        }
+        let player = board.next_player();
+        let maximizing = player == first_color;
+        let mut alpha = alpha;
+        let mut beta = beta;
+        let mut best = if maximizing { -2 } else { 2 };
+        let mut moves: Vec<Move> = self.all_liberties(board)
+            .iter()
+            .map(|c| Play(player, c.col, c.row))
+            .collect();
+        moves.push(Pass(player));
+        for &m in moves.iter() {
+            let mut b = board.clone();
+            let new_passes = if m.is_pass() {
+                b.tenuki();
+                passes + 1
+            } else {
+                if b.play(m).is_err() {
+                    continue;
+                }
+                0
+            };
+            let value = self.search(&b, depth + 1, new_passes, max_depth, alpha, beta);
+            if maximizing {
+                if value > best { best = value; }
+                if best > alpha { alpha = best; }
+            } else {
+                if value < best { best = value; }
+                if best < beta { beta = best; }
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        best
+    }
+
+    fn all_liberties(&self, board: &Board) -> Vec<Coord> {
+        let mut libs: Vec<Coord> = board.get_chain(self.first).unwrap().liberties()
+            .union(board.get_chain(self.second).unwrap().liberties())
+            .cloned()
+            .collect();
+        libs.sort();
+        libs
+    }
+}
+
+// Checks if a move turns a race the player would have won into one
+// that is lost or ends in seki, e.g. by filling one of the player's
+// own outside liberties instead of one of the opponent's.
+pub fn is_losing_move(board: &Board, m: &Move) -> bool {
+    if m.is_pass() || board.next_player() != *m.color() {
+        return false;
+    }
+    let coord = m.coord();
+    let color = *m.color();
+    let races = races_touching(board, coord, color);
+    if races.len() == 0 {
+        return false;
+    }
+    let mut after = board.clone();
+    if after.play(*m).is_err() {
+        return false;
+    }
+    races.iter().any(|&(own, other)| {
+        let won = Semeai::new(board, board.get_chain(own).unwrap(), board.get_chain(other).unwrap())
+            .map(|s| s.winner(color) == color)
+            .unwrap_or(false);
+        if !won {
+            return false;
+        }
+        match (after.get_chain(own), after.get_chain(other)) {
+            (Some(o), Some(t)) if o.color() == color && t.color() == color.opposite() =>
+                Semeai::new(&after, o, t)
+                    .map(|s| s.winner(color.opposite()) != color)
+                    .unwrap_or(false),
+            _ => false
+        }
+    })
+}
+
+// The races between the player's chains and the adjacent
+// opponent's chains, where one of the chains has a liberty at coord
+// and neither has too many liberties.
+fn races_touching(board: &Board, coord: Coord, color: Color) -> Vec<(Coord, Coord)> {
+    let mut races = Vec::new();
+    let own_chains = board.chains()
+        .iter()
+        .filter(|c| c.color() == color && c.liberties().len() <= MAX_LIBERTIES);
+    for own in own_chains {
+        let opponents: HashSet<usize> = own.coords()
+            .iter()
+            .flat_map(|&c| board.neighbours(c).iter())
+            .filter(|c| board.color(*c) == color.opposite())
+            .map(|c| board.chain_id(c))
+            .collect();
+        for &id in opponents.iter() {
+            let other = &board.chains()[id];
+            if other.liberties().len() > MAX_LIBERTIES {
+                continue;
+            }
+            if own.liberties().contains(&coord) || other.liberties().contains(&coord) {
+                races.push((own.coords()[0], other.coords()[0]));
+            }
+        }
+    }
+    races
+}
+
+fn count_liberties(board: &Board, own: Coord, other: Coord) -> Liberties {
+    let own_chain = board.get_chain(own).unwrap();
+    let other_chain = board.get_chain(other).unwrap();
+    let color = own_chain.color();
+    let shared = own_chain.liberties().intersection(other_chain.liberties()).count();
+    let eyes = own_chain.liberties()
+        .iter()
+        .filter(|lib| !other_chain.liberties().contains(*lib))
+        .filter(|&&lib| board.neighbours(lib).iter().all(|c| board.color(c) == color))
+        .count();
+    Liberties {
+        outside: own_chain.liberties().len() - shared - eyes,
+        shared:  shared,
+        eyes:    eyes
+    }
+}
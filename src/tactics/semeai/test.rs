@@ -0,0 +1,123 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Play;
+use board::White;
+use ruleset::Minimal;
+use super::Semeai;
+use super::is_losing_move;
+
+// A black and a white chain of two stones each in the lower left
+// corner, fighting it out.
+fn race_board<'a>(extra: Vec<(u8, u8)>) -> Board<'a> {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 1, 1));
+    b.play(Play(White, 1, 2));
+    b.play(Play(Black, 2, 1));
+    b.play(Play(White, 2, 2));
+    for &(col, row) in extra.iter() {
+        b.play(Play(Black, col, row));
+        b.tenuki();
+    }
+    b
+}
+
+fn semeai<'a>(b: &Board<'a>) -> Semeai<'a> {
+    let black = b.get_chain(Coord::new(1, 1)).unwrap();
+    let white = b.get_chain(Coord::new(1, 2)).unwrap();
+    Semeai::new(b, black, white).unwrap()
+}
+
+#[test]
+fn chains_of_the_same_color_are_no_race() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 1, 1));
+    b.play(Play(White, 5, 5));
+    b.play(Play(Black, 1, 3));
+    let first = b.get_chain(Coord::new(1, 1)).unwrap();
+    let second = b.get_chain(Coord::new(1, 3)).unwrap();
+    assert!(Semeai::new(&b, first, second).is_none());
+}
+
+#[test]
+fn chains_that_dont_touch_are_no_race() {
+    let mut b = Board::new(9, 6.5, Minimal);
+    b.play(Play(Black, 1, 1));
+    b.play(Play(White, 5, 5));
+    let first = b.get_chain(Coord::new(1, 1)).unwrap();
+    let second = b.get_chain(Coord::new(5, 5)).unwrap();
+    assert!(Semeai::new(&b, first, second).is_none());
+}
+
+#[test]
+fn liberties_counts_outside_liberties() {
+    let b = race_board(vec!((1, 3)));
+    let s = semeai(&b);
+    let black = s.liberties(Coord::new(2, 1));
+    assert_eq!(1, black.outside());
+    assert_eq!(0, black.shared());
+    assert_eq!(0, black.eyes());
+    let white = s.liberties(Coord::new(2, 2));
+    assert_eq!(2, white.outside());
+    assert_eq!(0, white.shared());
+    assert_eq!(0, white.eyes());
+}
+
+#[test]
+fn the_chain_with_more_liberties_wins_no_matter_who_moves_first() {
+    let b = race_board(vec!((1, 3)));
+    let s = semeai(&b);
+    assert_eq!(White, s.winner(Black));
+    assert_eq!(White, s.winner(White));
+}
+
+#[test]
+fn with_equal_liberties_the_player_to_move_wins() {
+    let b = race_board(vec!((1, 3), (2, 3)));
+    let s = semeai(&b);
+    assert_eq!(Black, s.winner(Black));
+    assert_eq!(White, s.winner(White));
+}
+
+#[test]
+fn a_race_with_shared_liberties_is_read_out() {
+    let b = race_board(vec!((1, 3), (2, 3), (3, 1)));
+    let s = semeai(&b);
+    assert_eq!(1, s.liberties(Coord::new(1, 1)).shared());
+    assert_eq!(Black, s.winner(Black));
+}
+
+#[test]
+fn filling_an_own_liberty_loses_the_race() {
+    let b = race_board(vec!((1, 3), (2, 3)));
+    assert!(is_losing_move(&b, &Play(Black, 3, 1)));
+}
+
+#[test]
+fn filling_the_opponents_liberty_wins_the_race() {
+    let b = race_board(vec!((1, 3), (2, 3)));
+    assert!(!is_losing_move(&b, &Play(Black, 3, 2)));
+}
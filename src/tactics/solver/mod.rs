@@ -0,0 +1,240 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+pub use self::Status::Alive;
+pub use self::Status::Dead;
+pub use self::Status::Ko;
+pub use self::Status::Seki;
+pub use self::Status::Unknown;
+use board::Board;
+use board::Color;
+use board::Coord;
+use board::Empty;
+use board::Move;
+use board::Pass;
+use board::Play;
+use tactics::seki::seki_chains;
+
+use std::cell::Cell;
+
+mod test;
+
+// Results of the search from the point of view of the defender
+static DEAD:  i8 = -1;
+static SEKI:  i8 = 0;
+static ALIVE: i8 = 1;
+
+// Regions and searches that are bigger than this are given up on,
+// e.g. for a chain that isn't enclosed at all.
+static MAX_REGION: usize = 20;
+static MAX_NODES:  usize = 200000;
+
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub enum Status {
+    Alive,
+    Dead,
+    Ko,
+    Seki,
+    Unknown
+}
+
+#[derive(Clone, Copy, Show, Eq, PartialEq)]
+pub struct Solution {
+    status:    Status,
+    best_move: Move
+}
+
+impl Solution {
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    // The best move for the player to move. A pass means that the
+    // player to move can't change the status of the target chain.
+    pub fn best_move(&self) -> Move {
+        self.best_move
+    }
+}
+
+// Solves the life and death problem of a single chain by searching
+// all moves inside a bounded region. A pass in the search stands for
+// a move elsewhere on the board.
+pub struct Solver<'a> {
+    board:     Board<'a>,
+    defender:  Color,
+    max_depth: usize,
+    nodes:     Cell<usize>,
+    region:    Vec<Coord>,
+    target:    Coord,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(board: &Board<'a>, region: Vec<Coord>, target: Coord) -> Solver<'a> {
+        let mut region = region;
+        region.sort();
+        region.dedup();
+        Solver {
+            board:     board.clone(),
+            defender:  board.color(&target),
+            max_depth: region.len() * 4 + 8,
+            nodes:     Cell::new(0),
+            region:    region,
+            target:    target,
+        }
+    }
+
+    // The empty intersections that can be reached from the target
+    // chain without crossing a stone of the attacker. For a chain
+    // that is surrounded this is the region that decides its life.
+    pub fn enclosed_region(board: &Board, target: Coord) -> Vec<Coord> {
+        let attacker = board.color(&target).opposite();
+        let mut visited = vec!(target);
+        let mut to_visit = vec!(target);
+        while to_visit.len() > 0 {
+            let coord = to_visit.pop().unwrap();
+            for &n in board.neighbours(coord).iter() {
+                if board.color(&n) != attacker && !visited.contains(&n) {
+                    visited.push(n);
+                    to_visit.push(n);
+                }
+            }
+        }
+        visited.into_iter().filter(|c| board.color(c) == Empty).collect()
+    }
+
+    // The status is Unknown if the region or the search is too big.
+    pub fn solve(&self) -> Solution {
+        let player = self.board.next_player();
+        let unknown = Solution { status: Unknown, best_move: Pass(player) };
+        if self.region.len() > MAX_REGION {
+            return unknown;
+        }
+        self.nodes.set(0);
+        let (value, best_move) = self.search(&self.board, 0, 0, DEAD, ALIVE, Empty);
+        let status = if value == ALIVE {
+            // Does the defender only live by winning a ko?
+            let (without_ko, _) = self.search(&self.board, 0, 0, DEAD, ALIVE, self.defender);
+            if without_ko == DEAD { Ko } else { Alive }
+        } else if value == SEKI {
+            Seki
+        } else {
+            // Does the attacker only kill by winning a ko?
+            let (without_ko, _) = self.search(&self.board, 0, 0, DEAD, ALIVE, self.defender.opposite());
+            if without_ko != DEAD { Ko } else { Dead }
+        };
+        if self.nodes.get() > MAX_NODES {
+            return unknown;
+        }
+        Solution {
+            status:    status,
+            best_move: best_move.unwrap_or(Pass(player))
+        }
+    }
+
+    // Alpha-beta search where the defender maximizes and the attacker
+    // minimizes. The player given as no_ko_captures isn't allowed to
+    // capture a ko, which is how we find out if a result depends on
+    // winning a ko.
+    fn search(&self, board: &Board, depth: usize, passes: usize, alpha: i8, beta: i8, no_ko_captures: Color) -> (i8, Option<Move>) {
+        self.nodes.set(self.nodes.get() + 1);
+        if self.nodes.get() > MAX_NODES {
+            // The result gets thrown away by solve
+            return (SEKI, None);
+        }
+        if board.color(&self.target) != self.defender {
+            return (DEAD, None);
+        }
+        if passes >= 2 || depth >= self.max_depth {
+            return (self.evaluate(board), None);
+        }
+        let player = board.next_player();
+        let maximizing = player == self.defender;
+        let mut alpha = alpha;
+        let mut beta = beta;
+        let mut best_value = if maximizing { DEAD - 1 } else { ALIVE + 1 };
+        let mut best_move = None;
+        for &m in self.candidates(board, player).iter() {
+            let mut b = board.clone();
+            let new_passes = if m.is_pass() {
+                b.tenuki();
+                // Playing elsewhere because of a ko ban doesn't end
+                // the local fight.
+                if board.ko().is_some() { 0 } else { passes + 1 }
+            } else {
+                if b.play(m).is_err() {
+                    continue;
+                }
+                if player == no_ko_captures && b.ko().is_some() {
+                    continue;
+                }
+                0
+            };
+            let (value, _) = self.search(&b, depth + 1, new_passes, alpha, beta, no_ko_captures);
+            if maximizing {
+                if value > best_value {
+                    best_value = value;
+                    best_move = Some(m);
+                }
+                if best_value > alpha {
+                    alpha = best_value;
+                }
+            } else {
+                if value < best_value {
+                    best_value = value;
+                    best_move = Some(m);
+                }
+                if best_value < beta {
+                    beta = best_value;
+                }
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        (best_value, best_move)
+    }
+
+    // Empty points of the region, the liberties of the target first,
+    // and a pass at the end.
+    fn candidates(&self, board: &Board, player: Color) -> Vec<Move> {
+        let libs = board.get_chain(self.target).unwrap().liberties();
+        let empty: Vec<&Coord> = self.region.iter().filter(|c| board.color(*c) == Empty).collect();
+        let mut moves: Vec<Move> = empty.iter()
+            .filter(|c| libs.contains(**c))
+            .map(|c| Play(player, c.col, c.row))
+            .collect();
+        moves.extend(empty.iter()
+            .filter(|c| !libs.contains(**c))
+            .map(|c| Play(player, c.col, c.row)));
+        moves.push(Pass(player));
+        moves
+    }
+
+    // The target chain survived until both players stopped playing
+    // locally. It's either in seki or alive.
+    fn evaluate(&self, board: &Board) -> i8 {
+        if seki_chains(board).contains(&board.chain_id(&self.target)) {
+            SEKI
+        } else {
+            ALIVE
+        }
+    }
+}
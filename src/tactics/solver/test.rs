@@ -0,0 +1,141 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::Board;
+use board::Coord;
+use board::Pass;
+use board::Play;
+use board::White;
+use ruleset::KgsChinese;
+use ruleset::Minimal;
+use super::Alive;
+use super::Dead;
+use super::Seki;
+use super::Solver;
+use super::Unknown;
+
+fn board_with<'a>(moves: &[(u8, u8)], black: &[(u8, u8)]) -> Board<'a> {
+    let mut b = Board::new(5, 6.5, Minimal);
+    for &(col, row) in moves.iter() {
+        b.play(Play(White, col, row));
+    }
+    for &(col, row) in black.iter() {
+        b.play(Play(Black, col, row));
+    }
+    b
+}
+
+// Black has a straight three in the corner and White is to move.
+fn straight_three<'a>() -> Board<'a> {
+    board_with(
+        &[(1, 3), (2, 3), (3, 3), (4, 3), (5, 3), (5, 2), (5, 1)],
+        &[(1, 2), (2, 2), (3, 2), (4, 2), (4, 1)])
+}
+
+fn solver_for<'a>(b: &Board<'a>) -> Solver<'a> {
+    let target = Coord::new(2, 2);
+    Solver::new(b, Solver::enclosed_region(b, target), target)
+}
+
+#[test]
+fn the_enclosed_region_contains_the_empty_points_inside_the_wall() {
+    let b = straight_three();
+    let mut region = Solver::enclosed_region(&b, Coord::new(2, 2));
+    region.sort();
+    assert_eq!(vec!(Coord::new(1, 1), Coord::new(2, 1), Coord::new(3, 1)), region);
+}
+
+#[test]
+fn a_chain_in_atari_is_dead() {
+    let b = board_with(&[(1, 3), (2, 3), (3, 3), (3, 2), (3, 1)], &[(1, 2), (2, 2), (2, 1)]);
+    let solution = solver_for(&b).solve();
+    assert_eq!(Dead, solution.status());
+    assert_eq!(Play(White, 1, 1), solution.best_move());
+}
+
+#[test]
+fn a_chain_with_three_eyes_is_alive() {
+    let b = board_with(
+        &[(1, 3), (2, 3), (3, 3), (4, 3), (5, 3)],
+        &[(2, 1), (4, 1), (1, 2), (2, 2), (3, 2), (4, 2), (5, 2)]);
+    let solution = solver_for(&b).solve();
+    assert_eq!(Alive, solution.status());
+    assert_eq!(Pass(White), solution.best_move());
+}
+
+#[test]
+fn the_attacker_kills_a_straight_three_on_the_vital_point() {
+    let b = straight_three();
+    let solution = solver_for(&b).solve();
+    assert_eq!(Dead, solution.status());
+    assert_eq!(Play(White, 2, 1), solution.best_move());
+}
+
+#[test]
+fn a_solver_can_be_reused() {
+    let b = straight_three();
+    let solver = solver_for(&b);
+    assert_eq!(Dead, solver.solve().status());
+    assert_eq!(Dead, solver.solve().status());
+}
+
+#[test]
+fn the_defender_lives_with_a_straight_three_on_the_vital_point() {
+    let mut b = straight_three();
+    b.play(Play(White, 5, 4));
+    let solution = solver_for(&b).solve();
+    assert_eq!(Alive, solution.status());
+    assert_eq!(Play(Black, 2, 1), solution.best_move());
+}
+
+#[test]
+fn chains_sharing_their_liberties_are_in_seki() {
+    let mut b = Board::new(7, 6.5, Minimal);
+    for &(col, row) in [(1, 1), (1, 2), (1, 3), (6, 3), (6, 2), (6, 1)].iter() {
+        b.play(Play(Black, col, row));
+    }
+    for col in range(1u8, 7) {
+        b.play(Play(Black, col, 4));
+    }
+    for &(col, row) in [(2, 1), (2, 2), (2, 3), (3, 3), (4, 3), (5, 3), (5, 2), (5, 1)].iter() {
+        b.play(Play(White, col, row));
+    }
+    b.play(Play(Black, 3, 2));
+    b.play(Play(Black, 4, 2));
+    let target = Coord::new(3, 2);
+    let solution = Solver::new(&b, Solver::enclosed_region(&b, target), target).solve();
+    assert_eq!(Seki, solution.status());
+    assert_eq!(Pass(White), solution.best_move());
+}
+
+#[test]
+fn a_chain_on_an_open_board_is_unknown() {
+    let mut b = Board::new(19, 6.5, KgsChinese);
+    b.play(Play(Black, 10, 10));
+    b.play(Play(White, 4, 4));
+    let target = Coord::new(10, 10);
+    let solution = Solver::new(&b, Solver::enclosed_region(&b, target), target).solve();
+    assert_eq!(Unknown, solution.status());
+    assert_eq!(Pass(Black), solution.best_move());
+}
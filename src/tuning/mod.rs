@@ -0,0 +1,158 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::White;
+use engine::McConfig;
+use engine::McEngine;
+use selfplay::Results;
+use selfplay::play_game;
+
+use std::num::Float;
+use std::rand::Rng;
+use std::rand::SeedableRng;
+use std::rand::XorShiftRng;
+use std::rand::random;
+
+mod test;
+
+// The tuned parameters of the search, the prior and the playouts and
+// the range they're tuned in.
+static PARAMETERS: [(&'static str, f32, f32); 14] = [
+    ("exploration",          0.0,  2.0),
+    ("rave_equivalence",     0.0,  5000.0),
+    ("prior_plays",          0.0,  200.0),
+    ("bias_weight",          0.0,  10.0),
+    ("widening_plays",       1.0,  500.0),
+    ("widening_factor",      1.05, 4.0),
+    ("prior_capture",        0.0,  0.5),
+    ("prior_atari_escape",   0.0,  0.5),
+    ("prior_pattern",        0.0,  0.5),
+    ("prior_near_last",      0.0,  0.5),
+    ("prior_first_line",     -0.5, 0.0),
+    ("prior_second_line",    -0.5, 0.0),
+    ("playout_pattern_rate", 0.0,  1.0),
+    ("playout_pattern_min",  0.0,  3.0)
+];
+
+// The usual SPSA gain sequence exponents.
+static ALPHA: f32 = 0.602;
+static GAMMA: f32 = 0.101;
+
+// Simultaneous perturbation stochastic approximation. Every
+// iteration perturbs all parameters at once in a random direction,
+// plays the two resulting configurations against each other and
+// moves the parameters towards the winner. The parameters are
+// scaled to [0, 1] so that the same step sizes work for all of them.
+pub struct Spsa {
+    a:         f32,
+    big_a:     f32,
+    c:         f32,
+    config:    McConfig,
+    iteration: usize,
+    rng:       XorShiftRng,
+    values:    Vec<f32>
+}
+
+impl Spsa {
+    pub fn new(config: McConfig, iterations: usize) -> Spsa {
+        Spsa::with_seed(config, iterations, [random(), random(), random(), random()])
+    }
+
+    pub fn with_seed(config: McConfig, iterations: usize, seed: [u32; 4]) -> Spsa {
+        let values = PARAMETERS.iter()
+            .map(|&(name, min, max)| ((config.get(name) - min) / (max - min)).max(0.0).min(1.0))
+            .collect();
+        Spsa {
+            a:         0.01,
+            big_a:     iterations as f32 / 10.0,
+            c:         0.05,
+            config:    config,
+            iteration: 0,
+            rng:       SeedableRng::from_seed(seed),
+            values:    values
+        }
+    }
+
+    // The current estimate of the best configuration.
+    pub fn config(&self) -> McConfig {
+        self.config_for(&self.values)
+    }
+
+    // The score is the result of the first configuration against
+    // the second one, between -1 (lost everything) and 1 (won
+    // everything).
+    pub fn step<F: FnMut(McConfig, McConfig) -> f32>(&mut self, mut score: F) {
+        let k = self.iteration as f32;
+        let a = self.a / (k + 1.0 + self.big_a).powf(ALPHA);
+        let c = self.c / (k + 1.0).powf(GAMMA);
+        let delta: Vec<f32> = range(0, self.values.len())
+            .map(|_| if self.rng.gen::<bool>() { 1.0 } else { -1.0 })
+            .collect();
+        let plus: Vec<f32> = self.values.iter().zip(delta.iter()).map(|(v, d)| v + c * *d).collect();
+        let minus: Vec<f32> = self.values.iter().zip(delta.iter()).map(|(v, d)| v - c * *d).collect();
+        let result = score(self.config_for(&plus), self.config_for(&minus));
+        self.values = self.values.iter()
+            .zip(delta.iter())
+            .map(|(v, d)| (v + a * result / (2.0 * c * *d)).max(0.0).min(1.0))
+            .collect();
+        self.iteration += 1;
+    }
+
+    fn config_for(&self, values: &Vec<f32>) -> McConfig {
+        let mut config = self.config;
+        for (&(name, min, max), value) in PARAMETERS.iter().zip(values.iter()) {
+            config.set(name, min + value.max(0.0).min(1.0) * (max - min));
+        }
+        config
+    }
+}
+
+// Tunes the parameters by playing the given number of games per
+// iteration on the board size. The configuration is written to the
+// file after every iteration, so that it can be loaded by the engine
+// even if the tuning gets stopped early.
+pub fn tune(config: McConfig, iterations: usize, games: usize, size: u8, path: Path) {
+    let mut spsa = Spsa::new(config, iterations);
+    for i in range(0, iterations) {
+        spsa.step(|plus, minus| {
+            let plus_engine = McEngine::with_config(plus);
+            let minus_engine = McEngine::with_config(minus);
+            let mut results = Results::new();
+            for j in range(0, games) {
+                if j % 2 == 0 {
+                    let (_, winner, _) = play_game(&plus_engine, &minus_engine, size, 6.5);
+                    results.add(winner, Black);
+                } else {
+                    let (_, winner, _) = play_game(&minus_engine, &plus_engine, size, 6.5);
+                    results.add(winner, White);
+                }
+            }
+            2.0 * results.win_rate() - 1.0
+        });
+        let current = spsa.config();
+        let values: Vec<String> = PARAMETERS.iter()
+            .map(|&(name, _, _)| format!("{} {:.3}", name, current.get(name)))
+            .collect();
+        println!("Iteration {}: {}", i + 1, values.connect(", "));
+        current.save(path.clone());
+    }
+}
@@ -0,0 +1,73 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use engine::McConfig;
+use super::Spsa;
+
+use std::num::Float;
+
+static SEED: [u32; 4] = [1, 2, 3, 4];
+
+#[test]
+fn the_parameters_move_towards_the_winning_configuration() {
+    let mut spsa = Spsa::with_seed(McConfig::new(), 100, SEED);
+    for _ in range(0us, 100) {
+        spsa.step(|plus, minus| if plus.exploration > minus.exploration { 1.0 } else { -1.0 });
+    }
+    assert!(spsa.config().exploration > McConfig::new().exploration);
+}
+
+#[test]
+fn the_prior_weights_are_tuned_too() {
+    let mut spsa = Spsa::with_seed(McConfig::new(), 100, SEED);
+    for _ in range(0us, 100) {
+        spsa.step(|plus, minus| if plus.prior_capture > minus.prior_capture { 1.0 } else { -1.0 });
+    }
+    assert!(spsa.config().prior_capture > McConfig::new().prior_capture);
+}
+
+#[test]
+fn the_parameters_stay_in_their_ranges() {
+    let mut spsa = Spsa::with_seed(McConfig::new(), 100, SEED);
+    for _ in range(0us, 200) {
+        spsa.step(|plus, minus| if plus.widening_factor < minus.widening_factor { 1.0 } else { -1.0 });
+    }
+    assert!(spsa.config().widening_factor >= 1.05);
+}
+
+#[test]
+fn untuned_parameters_are_left_alone() {
+    let mut config = McConfig::new();
+    config.playouts = 50;
+    let mut spsa = Spsa::with_seed(config, 10, SEED);
+    spsa.step(|_, _| 1.0);
+    assert_eq!(50, spsa.config().playouts);
+}
+
+#[test]
+fn without_any_steps_the_config_is_unchanged() {
+    let spsa = Spsa::with_seed(McConfig::new(), 10, SEED);
+    for name in McConfig::names().iter() {
+        assert!((spsa.config().get(*name) - McConfig::new().get(*name)).abs() < 0.001);
+    }
+}
@@ -0,0 +1,181 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+use board::Black;
+use board::Color;
+use board::White;
+use game::Game;
+use gtp::client::Client;
+use gtp::client::parse_move;
+use ruleset::KgsChinese;
+use selfplay::Results;
+use sgf::Writer;
+
+use std::io::fs::File;
+use time::precise_time_ns;
+
+mod test;
+
+// Plays a game between two external GTP engines. We keep track of
+// the game ourselves, so an engine that plays an illegal move,
+// refuses a legal move of its opponent or runs out of time loses.
+// Returns the game, the winner, the result in SGF notation and the
+// time both players used in seconds.
+pub fn play_game<'a>(black: &mut Client, white: &mut Client, size: u8, komi: f32, time: Option<f64>) -> (Game<'a>, Color, String, [f64; 2]) {
+    setup(black, size, komi, time);
+    setup(white, size, komi, time);
+    let mut game = Game::new(size, komi, KgsChinese);
+    let mut times = [0f64; 2];
+    let max_moves = size as usize * size as usize * 3;
+    while !game.is_over() && game.moves().len() < max_moves {
+        let color = game.next_player();
+        let index = if color == Black { 0 } else { 1 };
+        let (mover, other) = if color == Black { (&mut *black, &mut *white) } else { (&mut *white, &mut *black) };
+        match time {
+            Some(limit) => {
+                let _ = mover.send(format!("time_left {} {} 0", color.to_gtp(), (limit - times[index]) as u64).as_slice());
+            },
+            None => {}
+        }
+        let start = precise_time_ns();
+        let genmove = format!("genmove {}", color.to_gtp());
+        let response = match time {
+            Some(limit) => mover.send_with_timeout(genmove.as_slice(), limit - times[index]),
+            None        => mover.send(genmove.as_slice())
+        };
+        times[index] += (precise_time_ns() - start) as f64 / 1e9;
+        match time {
+            Some(limit) if times[index] > limit => return (game, color.opposite(), result(color.opposite(), "Time"), times),
+            _                                   => {}
+        }
+        let vertex: String = match response {
+            Ok(vertex) => vertex.as_slice().trim().chars().map(|c| c.to_lowercase()).collect(),
+            Err(_)     => return (game, color.opposite(), result(color.opposite(), "Forfeit"), times)
+        };
+        if vertex.as_slice() == "resign" {
+            return (game, color.opposite(), result(color.opposite(), "Resign"), times);
+        }
+        let m = match parse_move(color, vertex.as_slice(), size) {
+            Some(m) => m,
+            None    => return (game, color.opposite(), result(color.opposite(), "Forfeit"), times)
+        };
+        match game.play(m) {
+            Ok(g)  => game = g,
+            Err(_) => return (game, color.opposite(), result(color.opposite(), "Forfeit"), times)
+        }
+        if other.send(format!("play {} {}", color.to_gtp(), vertex).as_slice()).is_err() {
+            return (game, color, result(color, "Forfeit"), times);
+        }
+    }
+    let winner = game.winner();
+    let score = format!("{}", game.score());
+    (game, winner, score, times)
+}
+
+fn result(winner: Color, reason: &str) -> String {
+    let name = if winner == Black { "B" } else { "W" };
+    format!("{}+{}", name, reason)
+}
+
+fn setup(client: &mut Client, size: u8, komi: f32, time: Option<f64>) {
+    if client.send(format!("boardsize {}", size).as_slice()).is_err() {
+        panic!("The engine doesn't support the board size {}", size);
+    }
+    client.send("clear_board").unwrap();
+    client.send(format!("komi {}", komi).as_slice()).unwrap();
+    match time {
+        Some(limit) => {
+            let _ = client.send(format!("time_settings {} 0 0", limit as u64).as_slice());
+        },
+        None => {}
+    }
+}
+
+// Referees a match between two GTP engines, like gogui-twogtp does.
+// The time limit is the sudden death time per player and game in
+// seconds.
+pub struct TwoGtp {
+    first:       Client,
+    first_name:  String,
+    second:      Client,
+    second_name: String,
+    time:        Option<f64>
+}
+
+impl TwoGtp {
+    pub fn new(mut first: Client, mut second: Client, time: Option<f64>) -> TwoGtp {
+        let first_name = first.name();
+        let second_name = second.name();
+        TwoGtp {
+            first:       first,
+            first_name:  first_name,
+            second:      second,
+            second_name: second_name,
+            time:        time
+        }
+    }
+
+    // Plays the given number of games on each board size, alternating
+    // the colors. Prints a table of the results that is also written
+    // to results.dat in the SGF directory, together with the games.
+    pub fn run(&mut self, games: usize, sizes: &Vec<u8>, komi: f32, sgf_dir: Option<Path>) -> Results {
+        let mut results = Results::new();
+        let mut table = vec!(format!("{:>4} {:>4} {:<24} {:<24} {:<10} {:>5} {:>8} {:>8}",
+                                     "game", "size", "black", "white", "result", "moves", "time(B)", "time(W)"));
+        println!("{}", table[0]);
+        for &size in sizes.iter() {
+            for i in range(0, games) {
+                let color = if i % 2 == 0 { Black } else { White };
+                let (black, white, black_name, white_name) = if color == Black {
+                    (&mut self.first, &mut self.second, &self.first_name, &self.second_name)
+                } else {
+                    (&mut self.second, &mut self.first, &self.second_name, &self.first_name)
+                };
+                let (game, winner, result, times) = play_game(black, white, size, komi, self.time);
+                results.add(winner, color);
+                let row = format!("{:>4} {:>4} {:<24} {:<24} {:<10} {:>5} {:>8.1} {:>8.1}",
+                                  results.games(), size, black_name, white_name, result, game.moves().len(), times[0], times[1]);
+                println!("{}", row);
+                table.push(row);
+                match sgf_dir {
+                    Some(ref dir) => {
+                        let path = dir.join(format!("{}x{}-{:04}.sgf", size, size, i + 1));
+                        Writer::new(&game, black_name.as_slice(), white_name.as_slice())
+                            .with_result(result.as_slice())
+                            .save(path);
+                    },
+                    None => {}
+                }
+            }
+        }
+        let summary = format!("{} vs {}: {}", self.first_name, self.second_name, results.report());
+        println!("{}", summary);
+        table.push(summary);
+        match sgf_dir {
+            Some(ref dir) => {
+                let mut file = File::create(&dir.join("results.dat")).unwrap();
+                file.write_str(format!("{}\n", table.connect("\n")).as_slice()).unwrap();
+            },
+            None => {}
+        }
+        results
+    }
+}
@@ -0,0 +1,92 @@
+/************************************************************************
+ *                                                                      *
+ * Copyright 2015 Urban Hafner                                          *
+ *                                                                      *
+ * This file is part of Iomrascálaí.                                    *
+ *                                                                      *
+ * Iomrascálaí is free software: you can redistribute it and/or modify  *
+ * it under the terms of the GNU General Public License as published by *
+ * the Free Software Foundation, either version 3 of the License, or    *
+ * (at your option) any later version.                                  *
+ *                                                                      *
+ * Iomrascálaí is distributed in the hope that it will be useful,       *
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of       *
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the        *
+ * GNU General Public License for more details.                         *
+ *                                                                      *
+ * You should have received a copy of the GNU General Public License    *
+ * along with Iomrascálaí.  If not, see <http://www.gnu.org/licenses/>. *
+ *                                                                      *
+ ************************************************************************/
+
+#![cfg(test)]
+
+use board::Black;
+use board::White;
+use gtp::client::Client;
+use super::TwoGtp;
+use super::play_game;
+
+use std::io::TempDir;
+
+fn standin(vertex: &str) -> Client {
+    Client::spawn(format!("sh fixtures/gtp/standin.sh {}", vertex).as_slice()).unwrap()
+}
+
+#[test]
+fn a_game_between_passing_engines_is_scored_by_us() {
+    let (game, winner, result, _) = play_game(&mut standin("pass"), &mut standin("pass"), 9, 6.5, None);
+    assert_eq!(2, game.moves().len());
+    assert_eq!(White, winner);
+    assert_eq!("W+6.5", result.as_slice());
+}
+
+#[test]
+fn an_illegal_move_forfeits_the_game() {
+    let (game, winner, result, _) = play_game(&mut standin("d4"), &mut standin("pass"), 9, 6.5, None);
+    assert_eq!(2, game.moves().len());
+    assert_eq!(White, winner);
+    assert_eq!("W+Forfeit", result.as_slice());
+}
+
+#[test]
+fn resigning_loses_the_game() {
+    let (_, winner, result, _) = play_game(&mut standin("pass"), &mut standin("resign"), 9, 6.5, None);
+    assert_eq!(Black, winner);
+    assert_eq!("B+Resign", result.as_slice());
+}
+
+#[test]
+fn running_out_of_time_loses_the_game() {
+    let (game, winner, result, times) = play_game(&mut standin("pass"), &mut standin("pass"), 9, 6.5, Some(0.0));
+    assert_eq!(0, game.moves().len());
+    assert_eq!(White, winner);
+    assert_eq!("W+Time", result.as_slice());
+    assert!(times[0] > 0.0);
+}
+
+#[test]
+fn a_hanging_engine_loses_on_time() {
+    let (game, winner, result, times) = play_game(&mut standin("hang"), &mut standin("pass"), 9, 6.5, Some(1.0));
+    assert_eq!(0, game.moves().len());
+    assert_eq!(White, winner);
+    assert_eq!("W+Time", result.as_slice());
+    assert!(times[0] >= 1.0);
+}
+
+#[test]
+fn an_engine_can_be_started_without_the_shell() {
+    let mut client = Client::spawn_args(&Path::new("sh"), &[String::from_str("fixtures/gtp/standin.sh"), String::from_str("d4")]).unwrap();
+    assert_eq!(Ok(String::from_str("d4")), client.send("genmove b"));
+}
+
+#[test]
+fn run_writes_the_games_and_the_results_table() {
+    let mut twogtp = TwoGtp::new(standin("pass"), standin("pass"), None);
+    let dir = TempDir::new("twogtp").unwrap();
+    let results = twogtp.run(2, &vec!(9), 6.5, Some(dir.path().clone()));
+    assert_eq!(2, results.games());
+    assert_eq!(1, results.wins());
+    assert!(dir.path().join("9x9-0002.sgf").exists());
+    assert!(dir.path().join("results.dat").exists());
+}